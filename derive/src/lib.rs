@@ -0,0 +1,255 @@
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote;
+use syn::{
+    parse2, parse_quote, Data, DeriveInput, Error, Expr, FnArg, Ident, ItemFn, Pat, Type, Variant,
+};
+
+/// Adds an `UpdateOwner(mars_owner::OwnerUpdate)` variant to the ExecuteMsg enum.
+/// Must be placed before `#[cw_serde]` so the variant is included in the schema.
+#[proc_macro_attribute]
+pub fn owner_execute(metadata: TokenStream, input: TokenStream) -> TokenStream {
+    merge_variant(
+        metadata.into(),
+        input.into(),
+        parse_quote! {
+            /// Updates the owner role. See mars_owner::OwnerUpdate.
+            UpdateOwner(::mars_owner::OwnerUpdate)
+        },
+    )
+    .into()
+}
+
+/// Adds an `Owner {}` variant returning `mars_owner::OwnerResponse` to the QueryMsg enum.
+/// Must be placed before `#[cw_serde]` and `#[derive(QueryResponses)]`.
+#[proc_macro_attribute]
+pub fn owner_query(metadata: TokenStream, input: TokenStream) -> TokenStream {
+    merge_variant(
+        metadata.into(),
+        input.into(),
+        parse_quote! {
+            /// Returns the state of the owner role
+            #[returns(::mars_owner::OwnerResponse)]
+            Owner {}
+        },
+    )
+    .into()
+}
+
+/// Makes the handler fail with OwnerError::NotOwner unless the sender is the owner. Takes the
+/// Owner to check against, e.g. `#[only_owner(OWNER)]`. The handler needs `DepsMut` and
+/// `MessageInfo` parameters and an error type that OwnerError converts into.
+#[proc_macro_attribute]
+pub fn only_owner(metadata: TokenStream, input: TokenStream) -> TokenStream {
+    guard_handler(
+        metadata.into(),
+        input.into(),
+        "only_owner",
+        Ident::new("assert_owner", proc_macro2::Span::call_site()),
+    )
+    .into()
+}
+
+/// Same as #[only_owner] but requires the sender to be the emergency owner
+#[proc_macro_attribute]
+pub fn only_emergency_owner(metadata: TokenStream, input: TokenStream) -> TokenStream {
+    guard_handler(
+        metadata.into(),
+        input.into(),
+        "only_emergency_owner",
+        Ident::new("assert_emergency_owner", proc_macro2::Span::call_site()),
+    )
+    .into()
+}
+
+/// Prepends a call to the given Owner assertion to the body of the handler
+fn guard_handler(
+    metadata: TokenStream2,
+    input: TokenStream2,
+    macro_name: &str,
+    assertion: Ident,
+) -> TokenStream2 {
+    let owner: Expr = match parse2(metadata) {
+        Ok(owner) => owner,
+        Err(err) => {
+            return Error::new(
+                err.span(),
+                format!("expected the Owner to check, e.g. #[{}(OWNER)]", macro_name),
+            )
+            .to_compile_error()
+        }
+    };
+
+    let mut func: ItemFn = match parse2(input) {
+        Ok(func) => func,
+        Err(err) => return err.to_compile_error(),
+    };
+
+    let Some(deps) = find_param(&func, "DepsMut") else {
+        return Error::new_spanned(
+            &func.sig,
+            format!("#[{}] requires a DepsMut parameter", macro_name),
+        )
+        .to_compile_error();
+    };
+    let Some(info) = find_param(&func, "MessageInfo") else {
+        return Error::new_spanned(
+            &func.sig,
+            format!("#[{}] requires a MessageInfo parameter", macro_name),
+        )
+        .to_compile_error();
+    };
+
+    func.block.stmts.insert(
+        0,
+        parse_quote!(#owner.#assertion(#deps.storage, &#info.sender)?;),
+    );
+
+    quote!(#func)
+}
+
+/// Name of the first parameter whose type (or referenced type) is the given one, e.g. DepsMut
+fn find_param(func: &ItemFn, type_name: &str) -> Option<Ident> {
+    func.sig.inputs.iter().find_map(|arg| {
+        let FnArg::Typed(arg) = arg else {
+            return None;
+        };
+        let ty = match arg.ty.as_ref() {
+            Type::Reference(reference) => reference.elem.as_ref(),
+            ty => ty,
+        };
+        let Type::Path(path) = ty else {
+            return None;
+        };
+        if path.path.segments.last()?.ident != type_name {
+            return None;
+        }
+        match arg.pat.as_ref() {
+            Pat::Ident(pat) => Some(pat.ident.clone()),
+            _ => None,
+        }
+    })
+}
+
+/// Appends a variant to the enum the macro is applied to
+fn merge_variant(metadata: TokenStream2, input: TokenStream2, variant: Variant) -> TokenStream2 {
+    if !metadata.is_empty() {
+        return Error::new_spanned(metadata, "this macro takes no arguments").to_compile_error();
+    }
+
+    let mut ast: DeriveInput = match parse2(input) {
+        Ok(ast) => ast,
+        Err(err) => return err.to_compile_error(),
+    };
+
+    let Data::Enum(data) = &mut ast.data else {
+        return Error::new_spanned(&ast.ident, "only enums can be extended with owner messages")
+            .to_compile_error();
+    };
+    data.variants.push(variant);
+
+    quote!(#ast)
+}
+
+#[cfg(test)]
+mod tests {
+    use quote::quote;
+    use syn::{parse2, parse_quote, Data, DeriveInput, ItemFn, Stmt, Variant};
+
+    use crate::{guard_handler, merge_variant};
+
+    fn variant() -> Variant {
+        parse_quote!(UpdateOwner(::mars_owner::OwnerUpdate))
+    }
+
+    #[test]
+    fn appends_variant() {
+        let output = merge_variant(
+            quote!(),
+            quote! {
+                enum ExecuteMsg {
+                    Deposit {},
+                    Withdraw { amount: u128 },
+                }
+            },
+            variant(),
+        );
+
+        let ast: DeriveInput = parse2(output).unwrap();
+        let Data::Enum(data) = ast.data else {
+            panic!("Should still be an enum");
+        };
+        let names: Vec<_> = data.variants.iter().map(|v| v.ident.to_string()).collect();
+        assert_eq!(names, ["Deposit", "Withdraw", "UpdateOwner"]);
+        let (appended, expected) = (&data.variants[2], variant());
+        assert_eq!(quote!(#appended).to_string(), quote!(#expected).to_string());
+    }
+
+    #[test]
+    fn rejects_arguments_and_non_enums() {
+        let output = merge_variant(quote!(foo), quote! { enum ExecuteMsg {} }, variant());
+        assert!(output.to_string().contains("this macro takes no arguments"));
+
+        let output = merge_variant(quote!(), quote! { struct ExecuteMsg {} }, variant());
+        assert!(output
+            .to_string()
+            .contains("only enums can be extended with owner messages"));
+    }
+
+    #[test]
+    fn guards_handler() {
+        let output = guard_handler(
+            quote!(OWNER),
+            quote! {
+                fn execute_withdraw(
+                    deps: DepsMut,
+                    _env: Env,
+                    info: MessageInfo,
+                ) -> Result<Response, ContractError> {
+                    Ok(Response::new())
+                }
+            },
+            "only_owner",
+            parse_quote!(assert_owner),
+        );
+
+        let func: ItemFn = parse2(output).unwrap();
+        let expected: Stmt = parse_quote!(OWNER.assert_owner(deps.storage, &info.sender)?;);
+        assert_eq!(func.block.stmts.len(), 2);
+        let guard = &func.block.stmts[0];
+        assert_eq!(quote!(#guard).to_string(), quote!(#expected).to_string());
+    }
+
+    #[test]
+    fn rejects_handlers_without_deps_or_info() {
+        let output = guard_handler(
+            quote!(),
+            quote! { fn handler(deps: DepsMut, info: MessageInfo) {} },
+            "only_owner",
+            parse_quote!(assert_owner),
+        );
+        assert!(output
+            .to_string()
+            .contains("expected the Owner to check, e.g. #[only_owner(OWNER)]"));
+
+        let output = guard_handler(
+            quote!(OWNER),
+            quote! { fn handler(deps: Deps, info: MessageInfo) {} },
+            "only_owner",
+            parse_quote!(assert_owner),
+        );
+        assert!(output
+            .to_string()
+            .contains("#[only_owner] requires a DepsMut parameter"));
+
+        let output = guard_handler(
+            quote!(OWNER),
+            quote! { fn handler(deps: DepsMut, sender: Addr) {} },
+            "only_emergency_owner",
+            parse_quote!(assert_emergency_owner),
+        );
+        assert!(output
+            .to_string()
+            .contains("#[only_emergency_owner] requires a MessageInfo parameter"));
+    }
+}
@@ -0,0 +1,24 @@
+//! Prints the owner state machine for the enabled features, e.g.
+//! `cargo run --bin owner-diagram --features emergency-owner -- mermaid`
+
+use std::env;
+use std::process::exit;
+
+use mars_owner::{to_dot, to_mermaid, to_xstate_json};
+
+fn main() {
+    let format = env::args().nth(1).unwrap_or_else(|| "mermaid".to_string());
+    let diagram = match format.as_str() {
+        "mermaid" => to_mermaid(),
+        "dot" => to_dot(),
+        "xstate" => to_xstate_json(),
+        _ => {
+            eprintln!(
+                "Unknown format {}, expected one of: mermaid, dot, xstate",
+                format
+            );
+            exit(1);
+        }
+    };
+    print!("{}", diagram);
+}
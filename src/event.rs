@@ -0,0 +1,378 @@
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{Addr, Event, StdError, StdResult, Timestamp};
+use cw_utils::Expiration;
+
+use crate::owner::{OwnerPolicy, OwnerState};
+
+/// Event type emitted on every owner update. The chain prefixes it, so indexers see
+/// `wasm-mars_owner`.
+pub const OWNER_EVENT_TYPE: &str = "mars_owner";
+
+/// Typed form of the event emitted by Owner.update(). Can be parsed back from the emitted event
+/// with `OwnerEvent::try_from(&event)`.
+#[cw_serde]
+pub struct OwnerEvent {
+    pub namespace: String,
+    /// The OwnerUpdate variant that was executed, e.g. "propose_new_owner"
+    pub action: String,
+    pub sender: Addr,
+    pub previous_owner: Option<Addr>,
+    pub owner: Option<Addr>,
+    pub previous_proposed: Option<Addr>,
+    pub proposed: Option<Addr>,
+    pub previous_proposed_expiry: Option<Expiration>,
+    pub proposed_expiry: Option<Expiration>,
+    pub previous_accept_after: Option<Timestamp>,
+    pub accept_after: Option<Timestamp>,
+    pub previous_abolish_after: Option<Timestamp>,
+    pub abolish_after: Option<Timestamp>,
+    /// Members of the multi-owner group, empty for a single owner
+    pub previous_owners: Vec<Addr>,
+    pub owners: Vec<Addr>,
+    pub previous_threshold: Option<u32>,
+    pub threshold: Option<u32>,
+    #[cfg(feature = "emergency-owner")]
+    pub previous_emergency_owner: Option<Addr>,
+    #[cfg(feature = "emergency-owner")]
+    pub emergency_owner: Option<Addr>,
+}
+
+impl OwnerEvent {
+    /// Describes a successful transition from `previous` to `new_state`
+    pub(crate) fn transition(
+        namespace: &str,
+        action: &str,
+        sender: &Addr,
+        policy: &OwnerPolicy,
+        previous: &OwnerState,
+        new_state: &OwnerState,
+    ) -> Self {
+        OwnerEvent {
+            namespace: namespace.to_string(),
+            action: action.to_string(),
+            sender: sender.clone(),
+            previous_owner: previous.owner(),
+            owner: new_state.owner(),
+            previous_proposed: previous.proposed(),
+            proposed: new_state.proposed(),
+            previous_proposed_expiry: previous.proposed_expiry(),
+            proposed_expiry: new_state.proposed_expiry(),
+            previous_accept_after: previous.accept_after(policy),
+            accept_after: new_state.accept_after(policy),
+            previous_abolish_after: previous.abolish_after(),
+            abolish_after: new_state.abolish_after(),
+            previous_owners: group_owners(previous),
+            owners: group_owners(new_state),
+            previous_threshold: previous.threshold(),
+            threshold: new_state.threshold(),
+            #[cfg(feature = "emergency-owner")]
+            previous_emergency_owner: previous.emergency_owner(),
+            #[cfg(feature = "emergency-owner")]
+            emergency_owner: new_state.emergency_owner(),
+        }
+    }
+}
+
+fn group_owners(state: &OwnerState) -> Vec<Addr> {
+    match state.threshold() {
+        Some(_) => state.owners(),
+        None => vec![],
+    }
+}
+
+fn optional_value(addr: &Option<Addr>) -> String {
+    addr.as_ref()
+        .map_or_else(|| "None".to_string(), ToString::to_string)
+}
+
+/// Written as `at_height:<height>`, `at_time:<nanos>` or `never`
+fn expiry_value(expiry: &Option<Expiration>) -> String {
+    match expiry {
+        Some(Expiration::AtHeight(height)) => format!("at_height:{}", height),
+        Some(Expiration::AtTime(time)) => format!("at_time:{}", time.nanos()),
+        Some(Expiration::Never {}) => "never".to_string(),
+        None => "None".to_string(),
+    }
+}
+
+/// Timestamps are written in nanoseconds, like their JSON encoding
+fn time_value(time: &Option<Timestamp>) -> String {
+    time.map_or_else(|| "None".to_string(), |time| time.nanos().to_string())
+}
+
+fn owners_value(owners: &[Addr]) -> String {
+    if owners.is_empty() {
+        return "None".to_string();
+    }
+    owners
+        .iter()
+        .map(Addr::as_str)
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
+fn threshold_value(threshold: &Option<u32>) -> String {
+    threshold.map_or_else(|| "None".to_string(), |threshold| threshold.to_string())
+}
+
+impl From<OwnerEvent> for Event {
+    fn from(owner_event: OwnerEvent) -> Self {
+        let event = Event::new(OWNER_EVENT_TYPE)
+            .add_attribute("namespace", &owner_event.namespace)
+            .add_attribute("action", &owner_event.action)
+            .add_attribute("sender", &owner_event.sender)
+            .add_attribute(
+                "previous_owner",
+                optional_value(&owner_event.previous_owner),
+            )
+            .add_attribute("owner", optional_value(&owner_event.owner))
+            .add_attribute(
+                "previous_proposed",
+                optional_value(&owner_event.previous_proposed),
+            )
+            .add_attribute("proposed", optional_value(&owner_event.proposed))
+            .add_attribute(
+                "previous_proposed_expiry",
+                expiry_value(&owner_event.previous_proposed_expiry),
+            )
+            .add_attribute(
+                "proposed_expiry",
+                expiry_value(&owner_event.proposed_expiry),
+            )
+            .add_attribute(
+                "previous_accept_after",
+                time_value(&owner_event.previous_accept_after),
+            )
+            .add_attribute("accept_after", time_value(&owner_event.accept_after))
+            .add_attribute(
+                "previous_abolish_after",
+                time_value(&owner_event.previous_abolish_after),
+            )
+            .add_attribute("abolish_after", time_value(&owner_event.abolish_after))
+            .add_attribute(
+                "previous_owners",
+                owners_value(&owner_event.previous_owners),
+            )
+            .add_attribute("owners", owners_value(&owner_event.owners))
+            .add_attribute(
+                "previous_threshold",
+                threshold_value(&owner_event.previous_threshold),
+            )
+            .add_attribute("threshold", threshold_value(&owner_event.threshold));
+
+        #[cfg(feature = "emergency-owner")]
+        let event = event
+            .add_attribute(
+                "previous_emergency_owner",
+                optional_value(&owner_event.previous_emergency_owner),
+            )
+            .add_attribute(
+                "emergency_owner",
+                optional_value(&owner_event.emergency_owner),
+            );
+
+        event
+    }
+}
+
+impl TryFrom<&Event> for OwnerEvent {
+    type Error = StdError;
+
+    /// Accepts both the event as emitted by the contract and as reported by the chain
+    fn try_from(event: &Event) -> StdResult<Self> {
+        if event.ty != OWNER_EVENT_TYPE && event.ty != format!("wasm-{}", OWNER_EVENT_TYPE) {
+            return Err(StdError::parse_err(
+                "OwnerEvent",
+                format!("unexpected event type {}", event.ty),
+            ));
+        }
+
+        let value = |key: &str| -> StdResult<String> {
+            event
+                .attributes
+                .iter()
+                .find(|attr| attr.key == key)
+                .map(|attr| attr.value.clone())
+                .ok_or_else(|| {
+                    StdError::parse_err("OwnerEvent", format!("missing attribute {}", key))
+                })
+        };
+        let optional_addr = |key: &str| -> StdResult<Option<Addr>> {
+            let value = value(key)?;
+            Ok((value != "None").then(|| Addr::unchecked(value)))
+        };
+        let invalid = |key: &str, value: &str| {
+            StdError::parse_err("OwnerEvent", format!("invalid {} {}", key, value))
+        };
+        let number = |key: &str, value: &str| -> StdResult<u64> {
+            value.parse().map_err(|_| invalid(key, value))
+        };
+        let optional_expiry = |key: &str| -> StdResult<Option<Expiration>> {
+            let value = value(key)?;
+            let expiry = match value.split_once(':') {
+                _ if value == "None" => return Ok(None),
+                _ if value == "never" => Expiration::Never {},
+                Some(("at_height", height)) => Expiration::AtHeight(number(key, height)?),
+                Some(("at_time", nanos)) => {
+                    Expiration::AtTime(Timestamp::from_nanos(number(key, nanos)?))
+                }
+                _ => return Err(invalid(key, &value)),
+            };
+            Ok(Some(expiry))
+        };
+        let optional_time = |key: &str| -> StdResult<Option<Timestamp>> {
+            let value = value(key)?;
+            if value == "None" {
+                return Ok(None);
+            }
+            Ok(Some(Timestamp::from_nanos(number(key, &value)?)))
+        };
+        let owners = |key: &str| -> StdResult<Vec<Addr>> {
+            let value = value(key)?;
+            if value == "None" {
+                return Ok(vec![]);
+            }
+            Ok(value.split(',').map(Addr::unchecked).collect())
+        };
+        let optional_threshold = |key: &str| -> StdResult<Option<u32>> {
+            let value = value(key)?;
+            if value == "None" {
+                return Ok(None);
+            }
+            value.parse().map(Some).map_err(|_| invalid(key, &value))
+        };
+
+        Ok(OwnerEvent {
+            namespace: value("namespace")?,
+            action: value("action")?,
+            sender: Addr::unchecked(value("sender")?),
+            previous_owner: optional_addr("previous_owner")?,
+            owner: optional_addr("owner")?,
+            previous_proposed: optional_addr("previous_proposed")?,
+            proposed: optional_addr("proposed")?,
+            previous_proposed_expiry: optional_expiry("previous_proposed_expiry")?,
+            proposed_expiry: optional_expiry("proposed_expiry")?,
+            previous_accept_after: optional_time("previous_accept_after")?,
+            accept_after: optional_time("accept_after")?,
+            previous_abolish_after: optional_time("previous_abolish_after")?,
+            abolish_after: optional_time("abolish_after")?,
+            previous_owners: owners("previous_owners")?,
+            owners: owners("owners")?,
+            previous_threshold: optional_threshold("previous_threshold")?,
+            threshold: optional_threshold("threshold")?,
+            #[cfg(feature = "emergency-owner")]
+            previous_emergency_owner: optional_addr("previous_emergency_owner")?,
+            #[cfg(feature = "emergency-owner")]
+            emergency_owner: optional_addr("emergency_owner")?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::event::{OwnerEvent, OWNER_EVENT_TYPE};
+    use cosmwasm_std::{Addr, Event, StdError, Timestamp};
+    use cw_utils::Expiration;
+
+    fn owner_event() -> OwnerEvent {
+        OwnerEvent {
+            namespace: "xyz".to_string(),
+            action: "accept_proposed".to_string(),
+            sender: Addr::unchecked("miles_morales"),
+            previous_owner: Some(Addr::unchecked("peter_parker")),
+            owner: Some(Addr::unchecked("miles_morales")),
+            previous_proposed: Some(Addr::unchecked("miles_morales")),
+            proposed: None,
+            previous_proposed_expiry: Some(Expiration::AtTime(Timestamp::from_seconds(2000))),
+            proposed_expiry: None,
+            previous_accept_after: Some(Timestamp::from_nanos(1_000_000_001)),
+            accept_after: None,
+            previous_abolish_after: None,
+            abolish_after: None,
+            previous_owners: vec![],
+            owners: vec![],
+            previous_threshold: None,
+            threshold: None,
+            #[cfg(feature = "emergency-owner")]
+            previous_emergency_owner: None,
+            #[cfg(feature = "emergency-owner")]
+            emergency_owner: Some(Addr::unchecked("gwen_stacy")),
+        }
+    }
+
+    #[test]
+    fn event_round_trip() {
+        let event = Event::from(owner_event());
+        assert_eq!(event.ty, OWNER_EVENT_TYPE);
+        assert_eq!(OwnerEvent::try_from(&event).unwrap(), owner_event());
+
+        // As reported by the chain, with the prefix and the contract address
+        let mut event = event.add_attribute("_contract_address", "contract0");
+        event.ty = "wasm-mars_owner".to_string();
+        assert_eq!(OwnerEvent::try_from(&event).unwrap(), owner_event());
+    }
+
+    #[test]
+    fn multi_owner_event_round_trip() {
+        let owners = vec![
+            Addr::unchecked("peter_parker"),
+            Addr::unchecked("miles_morales"),
+        ];
+        let owner_event = OwnerEvent {
+            action: "propose_new_owner".to_string(),
+            previous_owner: None,
+            owner: None,
+            previous_proposed: None,
+            proposed: Some(Addr::unchecked("gwen_stacy")),
+            previous_proposed_expiry: None,
+            proposed_expiry: Some(Expiration::AtHeight(12345)),
+            previous_accept_after: None,
+            accept_after: Some(Timestamp::from_seconds(1000)),
+            previous_abolish_after: Some(Timestamp::from_seconds(500)),
+            abolish_after: None,
+            previous_owners: owners.clone(),
+            owners,
+            previous_threshold: Some(2),
+            threshold: Some(2),
+            ..owner_event()
+        };
+        let event = Event::from(owner_event.clone());
+        assert_eq!(OwnerEvent::try_from(&event).unwrap(), owner_event);
+
+        let owner_event = OwnerEvent {
+            proposed_expiry: Some(Expiration::Never {}),
+            ..owner_event
+        };
+        let event = Event::from(owner_event.clone());
+        assert_eq!(OwnerEvent::try_from(&event).unwrap(), owner_event);
+    }
+
+    #[test]
+    fn invalid_events_are_rejected() {
+        let event = Event::new("wasm-other").add_attribute("namespace", "xyz");
+        let err = OwnerEvent::try_from(&event).unwrap_err();
+        assert_eq!(
+            err,
+            StdError::parse_err("OwnerEvent", "unexpected event type wasm-other")
+        );
+
+        let event = Event::new(OWNER_EVENT_TYPE).add_attribute("namespace", "xyz");
+        let err = OwnerEvent::try_from(&event).unwrap_err();
+        assert_eq!(
+            err,
+            StdError::parse_err("OwnerEvent", "missing attribute action")
+        );
+
+        let mut event = Event::from(owner_event());
+        for attr in event.attributes.iter_mut() {
+            if attr.key == "proposed_expiry" {
+                attr.value = "at_block:5".to_string();
+            }
+        }
+        let err = OwnerEvent::try_from(&event).unwrap_err();
+        assert_eq!(
+            err,
+            StdError::parse_err("OwnerEvent", "invalid proposed_expiry at_block:5")
+        );
+    }
+}
@@ -0,0 +1,76 @@
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{Addr, BlockInfo, Order, StdResult, Storage, Timestamp};
+use cw_storage_plus::{Bound, Map};
+
+use crate::event::OwnerEvent;
+
+const DEFAULT_LIMIT: u32 = 10;
+const MAX_LIMIT: u32 = 30;
+
+/// A successful owner state transition. Returned from Owner.history()
+#[cw_serde]
+pub struct OwnerHistoryRecord {
+    pub id: u64,
+    /// The OwnerUpdate variant that was executed, e.g. "propose_new_owner"
+    pub action: String,
+    pub sender: Addr,
+    pub previous_owner: Option<Addr>,
+    pub owner: Option<Addr>,
+    pub proposed: Option<Addr>,
+    pub height: u64,
+    pub time: Timestamp,
+}
+
+/// Append-only log of owner state transitions, stored under its own key
+pub(crate) struct History(String);
+
+impl History {
+    pub fn new(namespace: &str) -> Self {
+        Self(format!("{}_history", namespace))
+    }
+
+    fn records(&self) -> Map<'_, u64, OwnerHistoryRecord> {
+        Map::new(&self.0)
+    }
+
+    /// Records the transition described by the event under the next free id
+    pub fn append(
+        &self,
+        storage: &mut dyn Storage,
+        block: &BlockInfo,
+        event: &OwnerEvent,
+    ) -> StdResult<()> {
+        let last = self
+            .records()
+            .keys(storage, None, None, Order::Descending)
+            .next()
+            .transpose()?;
+        let record = OwnerHistoryRecord {
+            id: last.map_or(0, |id| id + 1),
+            action: event.action.clone(),
+            sender: event.sender.clone(),
+            previous_owner: event.previous_owner.clone(),
+            owner: event.owner.clone(),
+            proposed: event.proposed.clone(),
+            height: block.height,
+            time: block.time,
+        };
+        self.records().save(storage, record.id, &record)
+    }
+
+    pub fn range(
+        &self,
+        storage: &dyn Storage,
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    ) -> StdResult<Vec<OwnerHistoryRecord>> {
+        let start = start_after.map(Bound::exclusive);
+        let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+
+        self.records()
+            .range(storage, start, None, Order::Ascending)
+            .take(limit)
+            .map(|item| Ok(item?.1))
+            .collect()
+    }
+}
@@ -0,0 +1,120 @@
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{to_binary, Addr, StdResult, Storage, WasmMsg};
+use cw_storage_plus::Item;
+
+use crate::OwnerEvent;
+
+/// Sent to every registered hook contract after a successful owner update
+#[cw_serde]
+pub struct OwnerChangedHook {
+    pub namespace: String,
+    /// The OwnerUpdate variant that was executed, e.g. "accept_proposed"
+    pub action: String,
+    pub sender: Addr,
+    pub previous_owner: Option<Addr>,
+    pub owner: Option<Addr>,
+    pub proposed: Option<Addr>,
+    #[cfg(feature = "emergency-owner")]
+    pub emergency_owner: Option<Addr>,
+}
+
+impl From<&OwnerEvent> for OwnerChangedHook {
+    fn from(event: &OwnerEvent) -> Self {
+        OwnerChangedHook {
+            namespace: event.namespace.clone(),
+            action: event.action.clone(),
+            sender: event.sender.clone(),
+            previous_owner: event.previous_owner.clone(),
+            owner: event.owner.clone(),
+            proposed: event.proposed.clone(),
+            #[cfg(feature = "emergency-owner")]
+            emergency_owner: event.emergency_owner.clone(),
+        }
+    }
+}
+
+/// The execute message hook contracts receive. They accept it by adding an
+/// `OwnerChanged(OwnerChangedHook)` variant to their own ExecuteMsg.
+#[cw_serde]
+pub enum OwnerHookMsg {
+    OwnerChanged(OwnerChangedHook),
+}
+
+/// Sent to a newly proposed owner, so a DAO or multisig knows it has to call AcceptProposed
+#[cw_serde]
+pub struct OwnershipProposed {
+    pub namespace: String,
+    /// The owner that proposed the transfer
+    pub from: Addr,
+}
+
+/// The execute message proposed owners receive, the sender being the contract to accept
+/// ownership of. They accept it by adding an `OwnershipProposed(OwnershipProposed)` variant to
+/// their own ExecuteMsg.
+#[cw_serde]
+pub enum OwnershipProposedMsg {
+    OwnershipProposed(OwnershipProposed),
+}
+
+impl OwnershipProposedMsg {
+    pub(crate) fn into_wasm_msg(self, proposed: &Addr) -> StdResult<WasmMsg> {
+        Ok(WasmMsg::Execute {
+            contract_addr: proposed.into(),
+            msg: to_binary(&self)?,
+            funds: vec![],
+        })
+    }
+}
+
+#[cw_serde]
+pub enum OwnerHooksUpdate {
+    /// Registers a contract to be notified of every owner update. Only owner can execute.
+    AddHook { addr: String },
+    /// Stops notifying a contract. Only owner can execute.
+    RemoveHook { addr: String },
+}
+
+/// Returned from Owner.query_hooks()
+#[cw_serde]
+pub struct HooksResponse {
+    pub hooks: Vec<String>,
+}
+
+/// Contracts notified of owner updates, stored under their own key
+pub(crate) struct Hooks(String);
+
+impl Hooks {
+    pub fn new(namespace: &str) -> Self {
+        Self(format!("{}_hooks", namespace))
+    }
+
+    fn item(&self) -> Item<'_, Vec<Addr>> {
+        Item::new(&self.0)
+    }
+
+    pub fn list(&self, storage: &dyn Storage) -> StdResult<Vec<Addr>> {
+        Ok(self.item().may_load(storage)?.unwrap_or_default())
+    }
+
+    pub fn save(&self, storage: &mut dyn Storage, hooks: Vec<Addr>) -> StdResult<()> {
+        self.item().save(storage, &hooks)
+    }
+
+    /// One message per registered hook, in the order they were added
+    pub fn messages(
+        &self,
+        storage: &dyn Storage,
+        hook: OwnerChangedHook,
+    ) -> StdResult<Vec<WasmMsg>> {
+        let msg = to_binary(&OwnerHookMsg::OwnerChanged(hook))?;
+        Ok(self
+            .list(storage)?
+            .into_iter()
+            .map(|addr| WasmMsg::Execute {
+                contract_addr: addr.into(),
+                msg: msg.clone(),
+                funds: vec![],
+            })
+            .collect())
+    }
+}
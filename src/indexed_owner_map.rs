@@ -0,0 +1,373 @@
+use std::fmt::Debug;
+
+use cosmwasm_std::{
+    Addr, Api, CustomQuery, DepsMut, Env, MessageInfo, Order, Response, StdResult, Storage,
+};
+use cw_storage_plus::{
+    Bound, Index, IndexList, IndexedMap, KeyDeserialize, MultiIndex, PrimaryKey,
+};
+use schemars::JsonSchema;
+
+use crate::owner::OwnerState;
+use crate::{OwnerError, OwnerInit, OwnerMap, OwnerResponse, OwnerUpdate};
+
+const DEFAULT_LIMIT: u32 = 10;
+const MAX_LIMIT: u32 = 30;
+
+type OwnerResult<T> = Result<T, OwnerError>;
+
+/// MultiIndex that leaves out states without the address, rather than indexing them under ""
+struct AddrIndex<'a, K> {
+    addr: fn(&OwnerState) -> Option<Addr>,
+    index: MultiIndex<'a, String, OwnerState, K>,
+}
+
+impl<'a, K> Index<OwnerState> for AddrIndex<'a, K> {
+    fn save(&self, store: &mut dyn Storage, pk: &[u8], data: &OwnerState) -> StdResult<()> {
+        if (self.addr)(data).is_none() {
+            return Ok(());
+        }
+        self.index.save(store, pk, data)
+    }
+
+    fn remove(&self, store: &mut dyn Storage, pk: &[u8], old_data: &OwnerState) -> StdResult<()> {
+        if (self.addr)(old_data).is_none() {
+            return Ok(());
+        }
+        self.index.remove(store, pk, old_data)
+    }
+}
+
+struct OwnerIndexes<'a, K> {
+    owner: AddrIndex<'a, K>,
+    proposed: AddrIndex<'a, K>,
+}
+
+impl<'a, K> IndexList<OwnerState> for OwnerIndexes<'a, K> {
+    fn get_indexes(&'_ self) -> Box<dyn Iterator<Item = &'_ dyn Index<OwnerState>> + '_> {
+        let v: Vec<&dyn Index<OwnerState>> = vec![&self.owner, &self.proposed];
+        Box::new(v.into_iter())
+    }
+}
+
+fn owner_idx(_pk: &[u8], state: &OwnerState) -> String {
+    state.owner().map(Addr::into_string).unwrap_or_default()
+}
+
+fn proposed_idx(_pk: &[u8], state: &OwnerState) -> String {
+    state.proposed().map(Addr::into_string).unwrap_or_default()
+}
+
+/// OwnerMap that also indexes keys by their current owner and by their proposed owner, so both can
+/// be listed without scanning the whole map. Stores states in the same layout as OwnerMap.
+///
+/// Owners of a multi-owner group are not indexed, as a key can only have one index entry. Keys
+/// without an owner or a proposed owner get no entry in that index, so they cost no extra write.
+pub struct IndexedOwnerMap<'a, K>
+where
+    K: PrimaryKey<'a>,
+{
+    map: OwnerMap<'a, K>,
+    states: IndexedMap<'a, K, OwnerState, OwnerIndexes<'a, K>>,
+}
+
+impl<'a, K> IndexedOwnerMap<'a, K>
+where
+    K: PrimaryKey<'a> + KeyDeserialize + Clone,
+    K::Output: 'static,
+{
+    /// Takes the namespace of the states and one namespace for each of the two indexes
+    pub fn new(namespace: &'a str, owner_namespace: &'a str, proposed_namespace: &'a str) -> Self {
+        Self {
+            map: OwnerMap::new(namespace),
+            states: IndexedMap::new(
+                namespace,
+                OwnerIndexes {
+                    owner: AddrIndex {
+                        addr: OwnerState::owner,
+                        index: MultiIndex::new(owner_idx, namespace, owner_namespace),
+                    },
+                    proposed: AddrIndex {
+                        addr: OwnerState::proposed,
+                        index: MultiIndex::new(proposed_idx, namespace, proposed_namespace),
+                    },
+                },
+            ),
+        }
+    }
+
+    //--------------------------------------------------------------------------------------------------
+    // Queries
+    //--------------------------------------------------------------------------------------------------
+    pub fn current(&self, storage: &dyn Storage, key: K) -> StdResult<Option<Addr>> {
+        self.map.current(storage, key)
+    }
+
+    pub fn is_owner(&self, storage: &dyn Storage, key: K, addr: &Addr) -> StdResult<bool> {
+        self.map.is_owner(storage, key, addr)
+    }
+
+    pub fn proposed(&self, storage: &dyn Storage, key: K) -> StdResult<Option<Addr>> {
+        self.map.proposed(storage, key)
+    }
+
+    pub fn is_proposed(&self, storage: &dyn Storage, key: K, addr: &Addr) -> StdResult<bool> {
+        self.map.is_proposed(storage, key, addr)
+    }
+
+    #[cfg(feature = "emergency-owner")]
+    pub fn emergency_owner(&self, storage: &dyn Storage, key: K) -> StdResult<Option<Addr>> {
+        self.map.emergency_owner(storage, key)
+    }
+
+    #[cfg(feature = "emergency-owner")]
+    pub fn is_emergency_owner(
+        &self,
+        storage: &dyn Storage,
+        key: K,
+        addr: &Addr,
+    ) -> StdResult<bool> {
+        self.map.is_emergency_owner(storage, key, addr)
+    }
+
+    pub fn query(&self, storage: &dyn Storage, key: K) -> StdResult<OwnerResponse> {
+        self.map.query(storage, key)
+    }
+
+    /// Keys the address is the single owner of. Unlike OwnerMap.scan_owned_by(), keys held by a
+    /// multi-owner group are not listed for its members.
+    pub fn owned_by(
+        &self,
+        storage: &dyn Storage,
+        owner: &Addr,
+        start_after: Option<K>,
+        limit: Option<u32>,
+    ) -> StdResult<Vec<K::Output>> {
+        let start = start_after.map(Bound::exclusive);
+        let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+
+        self.states
+            .idx
+            .owner
+            .index
+            .prefix(owner.to_string())
+            .keys(storage, start, None, Order::Ascending)
+            .take(limit)
+            .collect()
+    }
+
+    /// Keys where the address has been proposed as the new owner and has yet to accept
+    pub fn pending_for(
+        &self,
+        storage: &dyn Storage,
+        proposed: &Addr,
+        start_after: Option<K>,
+        limit: Option<u32>,
+    ) -> StdResult<Vec<K::Output>> {
+        let start = start_after.map(Bound::exclusive);
+        let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+
+        self.states
+            .idx
+            .proposed
+            .index
+            .prefix(proposed.to_string())
+            .keys(storage, start, None, Order::Ascending)
+            .take(limit)
+            .collect()
+    }
+
+    //--------------------------------------------------------------------------------------------------
+    // Mutations
+    //--------------------------------------------------------------------------------------------------
+    /// Sets up the owner of a new key. Can only be called once per key.
+    pub fn initialize(
+        &self,
+        storage: &mut dyn Storage,
+        api: &dyn Api,
+        key: K,
+        init_action: OwnerInit,
+    ) -> OwnerResult<()> {
+        let new_state = self
+            .map
+            .init_state(storage, api, key.clone(), init_action)?;
+        self.states.save(storage, key, &new_state)?;
+        Ok(())
+    }
+
+    /// Composes execute responses for owner state updates of a key
+    pub fn update<C, Q: CustomQuery>(
+        &self,
+        deps: DepsMut<Q>,
+        env: Env,
+        info: MessageInfo,
+        key: K,
+        update: OwnerUpdate,
+    ) -> OwnerResult<Response<C>>
+    where
+        C: Clone + Debug + PartialEq + JsonSchema,
+    {
+        let (previous, new_state, policy) = self.map.next_state(
+            deps.storage,
+            deps.api,
+            &env.block,
+            &info.sender,
+            key.clone(),
+            update.clone(),
+        )?;
+        self.states.save(deps.storage, key, &new_state)?;
+
+        Ok(self
+            .map
+            .response(&update, &info.sender, &policy, &previous, &new_state)?)
+    }
+
+    //--------------------------------------------------------------------------------------------------
+    // Assertions
+    //--------------------------------------------------------------------------------------------------
+    /// Similar to is_owner() except it raises an exception if caller is not the key's owner
+    pub fn assert_owner(&self, storage: &dyn Storage, key: K, caller: &Addr) -> OwnerResult<()> {
+        self.map.assert_owner(storage, key, caller)
+    }
+
+    pub fn assert_proposed(&self, storage: &dyn Storage, key: K, caller: &Addr) -> OwnerResult<()> {
+        self.map.assert_proposed(storage, key, caller)
+    }
+
+    #[cfg(feature = "emergency-owner")]
+    pub fn assert_emergency_owner(
+        &self,
+        storage: &dyn Storage,
+        key: K,
+        caller: &Addr,
+    ) -> OwnerResult<()> {
+        self.map.assert_emergency_owner(storage, key, caller)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use cosmwasm_std::testing::{mock_dependencies, mock_env, mock_info};
+    use cosmwasm_std::{Addr, DepsMut, Empty, Order};
+
+    use crate::OwnerUpdate::{AcceptProposed, ClearProposed, ProposeNewOwner};
+    use crate::{IndexedOwnerMap, OwnerInit, OwnerUpdate};
+
+    fn vault_owners() -> IndexedOwnerMap<'static, u64> {
+        IndexedOwnerMap::new(
+            "vault_owners",
+            "vault_owners__owner",
+            "vault_owners__proposed",
+        )
+    }
+
+    fn update(deps: DepsMut, sender: &str, vault: u64, update: OwnerUpdate) {
+        vault_owners()
+            .update::<Empty, Empty>(deps, mock_env(), mock_info(sender, &[]), vault, update)
+            .unwrap();
+    }
+
+    #[test]
+    fn lists_keys_by_owner() {
+        let mut deps = mock_dependencies();
+        let mut_deps = deps.as_mut();
+        for vault in 0..15 {
+            let owner = if vault % 3 == 0 {
+                "miles_morales"
+            } else {
+                "peter_parker"
+            };
+            vault_owners()
+                .initialize(
+                    mut_deps.storage,
+                    mut_deps.api,
+                    vault,
+                    OwnerInit::SetInitialOwner {
+                        owner: owner.to_string(),
+                    },
+                )
+                .unwrap();
+        }
+
+        let storage = deps.as_ref().storage;
+        let peter = Addr::unchecked("peter_parker");
+        let owned = vault_owners()
+            .owned_by(storage, &peter, None, None)
+            .unwrap();
+        assert_eq!(owned, [1, 2, 4, 5, 7, 8, 10, 11, 13, 14]);
+
+        let miles = Addr::unchecked("miles_morales");
+        let owned = vault_owners()
+            .owned_by(storage, &miles, Some(3), Some(2))
+            .unwrap();
+        assert_eq!(owned, [6, 9]);
+
+        let gwen = Addr::unchecked("gwen_stacy");
+        let owned = vault_owners().owned_by(storage, &gwen, None, None).unwrap();
+        assert!(owned.is_empty());
+    }
+
+    #[test]
+    fn indexes_follow_transfers() {
+        let mut deps = mock_dependencies();
+        let mut_deps = deps.as_mut();
+        for vault in 0..3 {
+            vault_owners()
+                .initialize(
+                    mut_deps.storage,
+                    mut_deps.api,
+                    vault,
+                    OwnerInit::SetInitialOwner {
+                        owner: "peter_parker".to_string(),
+                    },
+                )
+                .unwrap();
+        }
+
+        for vault in 0..3 {
+            update(
+                deps.as_mut(),
+                "peter_parker",
+                vault,
+                ProposeNewOwner {
+                    proposed: "miles_morales".to_string(),
+                    expiry: None,
+                },
+            );
+        }
+        update(deps.as_mut(), "peter_parker", 1, ClearProposed);
+        update(deps.as_mut(), "miles_morales", 2, AcceptProposed);
+
+        let storage = deps.as_ref().storage;
+        let peter = Addr::unchecked("peter_parker");
+        let miles = Addr::unchecked("miles_morales");
+        let pending = vault_owners()
+            .pending_for(storage, &miles, None, None)
+            .unwrap();
+        assert_eq!(pending, [0]);
+        let owned = vault_owners()
+            .owned_by(storage, &miles, None, None)
+            .unwrap();
+        assert_eq!(owned, [2]);
+        let owned = vault_owners()
+            .owned_by(storage, &peter, None, None)
+            .unwrap();
+        assert_eq!(owned, [0, 1]);
+
+        // Same storage layout as OwnerMap
+        let res = vault_owners().query(storage, 2).unwrap();
+        assert_eq!(res.owner, Some("miles_morales".to_string()));
+        assert_eq!(res.proposed, None);
+
+        // Keys without a proposed owner are left out of the index
+        let unindexed = vault_owners()
+            .states
+            .idx
+            .proposed
+            .index
+            .prefix(String::new())
+            .keys(storage, None, None, Order::Ascending)
+            .count();
+        assert_eq!(unindexed, 0);
+    }
+}
@@ -1,3 +1,36 @@
+// Lets the message macros, which refer to ::mars_owner, be used within this crate
+#[cfg(any(test, feature = "testing"))]
+extern crate self as mars_owner;
+
+mod event;
+mod history;
+mod hooks;
+mod indexed_owner_map;
 mod owner;
+mod owner_map;
+#[cfg(test)]
+mod proptests;
+mod roles;
+#[cfg(feature = "testing")]
+pub mod testing;
+mod transitions;
 
-pub use owner::{Owner, OwnerError, OwnerInit, OwnerResponse, OwnerUpdate};
+pub use event::{OwnerEvent, OWNER_EVENT_TYPE};
+pub use history::OwnerHistoryRecord;
+pub use hooks::{
+    HooksResponse, OwnerChangedHook, OwnerHookMsg, OwnerHooksUpdate, OwnershipProposed,
+    OwnershipProposedMsg,
+};
+pub use indexed_owner_map::IndexedOwnerMap;
+pub use mars_owner_derive::{only_emergency_owner, only_owner, owner_execute, owner_query};
+#[cfg(feature = "emergency-owner")]
+pub use owner::EmergencyOwnerAuth;
+pub use owner::{
+    CwOwnableAction, CwOwnableOwnership, MultiOwnerResponse, Owner, OwnerAuth, OwnerError,
+    OwnerInit, OwnerResponse, OwnerUpdate, PendingApproval, ABOLITION_DELAY, MAX_PENDING_APPROVALS,
+};
+pub use owner_map::OwnerMap;
+pub use roles::{RoleResponse, Roles, RolesError, RolesUpdate};
+pub use transitions::{
+    to_dot, to_mermaid, to_xstate_json, Transition, FINAL_STATE, INITIAL_STATE, STATES, TRANSITIONS,
+};
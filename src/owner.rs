@@ -1,22 +1,78 @@
-use std::fmt::Debug;
+use std::fmt::{self, Debug};
+use std::marker::PhantomData;
 
 use cosmwasm_schema::cw_serde;
 use cosmwasm_std::{
-    Addr, Api, CustomQuery, DepsMut, MessageInfo, Response, StdError, StdResult, Storage,
+    from_slice, to_binary, to_vec, Addr, Api, Binary, BlockInfo, CustomQuery, DepsMut, Env,
+    MessageInfo, Response, StdError, StdResult, Storage, Timestamp,
 };
-use cw_storage_plus::Item;
+use cw_storage_plus::{Item, SnapshotItem, Strategy};
+use cw_utils::Expiration;
 use schemars::JsonSchema;
 use thiserror::Error;
 
+use crate::event::OwnerEvent;
+use crate::history::{History, OwnerHistoryRecord};
+use crate::hooks::{
+    Hooks, HooksResponse, OwnerChangedHook, OwnerHooksUpdate, OwnershipProposed,
+    OwnershipProposedMsg,
+};
+
 /// Returned from Owner.query()
 #[cw_serde]
 pub struct OwnerResponse {
+    /// Tells apart multiple Owner instances in one contract. Defaults to the namespace.
+    pub label: String,
     pub owner: Option<String>,
     pub proposed: Option<String>,
+    pub proposed_expiry: Option<Expiration>,
+    /// Earliest time the proposed owner can accept the role
+    pub accept_after: Option<Timestamp>,
     pub initialized: bool,
     pub abolished: bool,
+    /// Set while abolition of the owner role is pending. It can be confirmed from then on.
+    pub abolish_after: Option<Timestamp>,
     #[cfg(feature = "emergency-owner")]
     pub emergency_owner: Option<String>,
+    /// Set when the owner role is held by a group of addresses
+    pub multi_owner: Option<MultiOwnerResponse>,
+}
+
+#[cw_serde]
+pub struct MultiOwnerResponse {
+    pub owners: Vec<String>,
+    pub threshold: u32,
+    pub pending: Vec<PendingApproval>,
+}
+
+/// Approvals collected for an action that has not yet reached the multi-owner threshold
+#[cw_serde]
+pub struct PendingApproval {
+    pub action: String,
+    pub approvals: Vec<Addr>,
+}
+
+/// Proof that the caller was checked to be the owner. Can only be obtained from
+/// Owner.authorize_owner(), so privileged functions taking it can't be reached without the check.
+/// Carries the marker type of the Owner it came from (see Owner.branded()), so a token of one
+/// Owner can't be used where another's is required. Tokens of unbranded Owners are all alike.
+pub struct OwnerAuth<M = ()>(PhantomData<M>);
+
+impl<M> Debug for OwnerAuth<M> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("OwnerAuth")
+    }
+}
+
+/// Proof that the caller was checked to be the emergency owner. See OwnerAuth.
+#[cfg(feature = "emergency-owner")]
+pub struct EmergencyOwnerAuth<M = ()>(PhantomData<M>);
+
+#[cfg(feature = "emergency-owner")]
+impl<M> Debug for EmergencyOwnerAuth<M> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("EmergencyOwnerAuth")
+    }
 }
 
 /// Errors returned from Owner state transitions
@@ -25,14 +81,62 @@ pub enum OwnerError {
     #[error("{0}")]
     Std(#[from] StdError),
 
-    #[error("Caller is not owner")]
-    NotOwner {},
+    #[error("Caller is not owner: expected {expected}, got {actual}")]
+    NotOwner { expected: String, actual: String },
+
+    #[error("Caller is not the proposed owner: expected {expected}, got {actual}")]
+    NotProposedOwner { expected: String, actual: String },
+
+    #[error("Owner state transition was not valid: cannot {attempted} in the {state} state")]
+    StateTransitionError { state: String, attempted: String },
+
+    #[error("Proposal expiry is already in the past")]
+    InvalidExpiry {},
+
+    #[error("Proposal expiry must be a time while an acceptance timelock is set")]
+    InvalidExpiryHeight {},
+
+    #[error("Proposal to change the owner has expired")]
+    ProposalExpired {},
+
+    #[error("Proposal cannot be accepted before {accept_after}")]
+    TimelockNotElapsed { accept_after: Timestamp },
+
+    #[error("Threshold must be between 1 and the number of owners")]
+    InvalidThreshold {},
+
+    #[error("Owner listed more than once")]
+    DuplicateOwner {},
+
+    #[error("Caller has already approved this action")]
+    AlreadyApproved {},
+
+    #[error("Caller has not approved this action")]
+    ApprovalNotFound {},
+
+    #[error("Caller already has {max} pending approvals")]
+    TooManyPendingApprovals { max: u32 },
+
+    #[error("Owner role cannot be abolished before {abolish_after}")]
+    AbolitionTimelockNotElapsed { abolish_after: Timestamp },
+
+    #[error("No legacy owner stored under key {key}")]
+    LegacyOwnerNotFound { key: String },
+
+    #[error("Legacy ownership has a pending owner but no current owner")]
+    InvalidLegacyOwnership {},
+
+    #[error("Owner keeps a history or snapshots, use initialize_with_env() instead")]
+    EnvRequired {},
+
+    #[error("Hooks are not enabled for this Owner")]
+    HooksDisabled {},
 
-    #[error("Caller is not the proposed owner")]
-    NotProposedOwner {},
+    #[error("{addr} is already registered as a hook")]
+    HookAlreadyRegistered { addr: String },
 
-    #[error("Owner state transition was not valid")]
-    StateTransitionError {},
+    #[error("{addr} is not registered as a hook")]
+    HookNotRegistered { addr: String },
 
     #[cfg(feature = "emergency-owner")]
     #[error("Caller is not the emergency owner")]
@@ -41,9 +145,51 @@ pub enum OwnerError {
 
 type OwnerResult<T> = Result<T, OwnerError>;
 
+impl OwnerError {
+    fn not_owner(expected: &[Addr], actual: &Addr) -> Self {
+        OwnerError::NotOwner {
+            expected: join_addrs(expected),
+            actual: actual.to_string(),
+        }
+    }
+
+    fn not_proposed_owner(expected: Option<Addr>, actual: &Addr) -> Self {
+        OwnerError::NotProposedOwner {
+            expected: join_addrs(expected.as_slice()),
+            actual: actual.to_string(),
+        }
+    }
+
+    fn state_transition(state: &str, attempted: &str) -> Self {
+        OwnerError::StateTransitionError {
+            state: state.to_string(),
+            attempted: attempted.to_string(),
+        }
+    }
+}
+
+fn join_addrs(addrs: &[Addr]) -> String {
+    if addrs.is_empty() {
+        return "None".to_string();
+    }
+    addrs
+        .iter()
+        .map(Addr::as_str)
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// Minimum cooling-off period (in seconds) between announcing and confirming abolition of the
+/// owner role. If the acceptance timelock is longer, that is used instead.
+pub const ABOLITION_DELAY: u64 = 86_400;
+
+/// Maximum number of pending actions a single owner can have approved in multi-owner mode. Keeps
+/// one owner from growing the state, which is loaded on every call, without bound.
+pub const MAX_PENDING_APPROVALS: u32 = 10;
+
 /// The finite states that are possible
 #[cw_serde]
-enum OwnerState {
+pub(crate) enum OwnerState {
     Uninitialized,
     Base {
         owner: Addr,
@@ -53,186 +199,244 @@ enum OwnerState {
     Proposed {
         owner: Addr,
         proposed: Addr,
+        expiry: Option<Expiration>,
+        #[serde(default)]
+        proposed_at: Timestamp,
+        #[cfg(feature = "emergency-owner")]
+        emergency_owner: Option<Addr>,
+    },
+    AbolitionProposed {
+        owner: Addr,
+        abolish_after: Timestamp,
         #[cfg(feature = "emergency-owner")]
         emergency_owner: Option<Addr>,
     },
     Abolished,
+    MultiOwner(MultiOwnerState),
 }
 
+/// Owner role held by a group of addresses. Privileged updates only go through once `threshold`
+/// of the owners have approved the exact same update.
 #[cw_serde]
-pub enum OwnerUpdate {
-    /// Proposes a new owner to take role. Only current owner can execute.
-    ProposeNewOwner { proposed: String },
-    /// Clears the currently proposed owner. Only current owner can execute.
-    ClearProposed,
-    /// Promotes the proposed owner to be the current one. Only the proposed owner can execute.
-    AcceptProposed,
-    /// Throws away the keys to the Owner role forever. Once done, no owner can ever be set later.
-    AbolishOwnerRole,
-    #[cfg(feature = "emergency-owner")]
-    /// A separate entity managed by Owner that can be used for granting specific emergency powers.
-    SetEmergencyOwner { emergency_owner: String },
+pub(crate) struct MultiOwnerState {
+    owners: Vec<Addr>,
+    threshold: u32,
+    pending: Vec<PendingApproval>,
+    proposed: Option<Addr>,
+    expiry: Option<Expiration>,
+    #[serde(default)]
+    proposed_at: Timestamp,
+    #[serde(default)]
+    abolish_after: Option<Timestamp>,
     #[cfg(feature = "emergency-owner")]
-    /// Remove the entity in the Emergency Owner role
-    ClearEmergencyOwner,
+    emergency_owner: Option<Addr>,
 }
 
-#[cw_serde]
-pub enum OwnerInit {
-    /// Sets the initial owner when none. No restrictions permissions to modify.
-    SetInitialOwner { owner: String },
-    /// Throws away the keys to the Owner role forever. Once done, no owner can ever be set later.
-    AbolishOwnerRole,
-}
-
-/// A struct designed to help facilitate a two-step transition between contract owners safely.
-/// It implements a finite state machine with dispatched events to manage state transitions.
-/// State machine visualization: https://stately.ai/registry/editor/b7e5dbac-2d33-47f7-a84b-e38dff5694ad?machineId=f8d99cd1-dd55-4506-961b-e2542480be68&mode=Simulate
-pub struct Owner<'a>(Item<'a, OwnerState>);
-
-impl<'a> Owner<'a> {
-    pub const fn new(namespace: &'a str) -> Self {
-        Self(Item::new(namespace))
-    }
-
-    fn state(&self, storage: &'a dyn Storage) -> StdResult<OwnerState> {
-        Ok(self
-            .0
-            .may_load(storage)?
-            .unwrap_or(OwnerState::Uninitialized))
+impl OwnerState {
+    pub(crate) fn name(&self) -> &'static str {
+        match self {
+            OwnerState::Uninitialized => "Uninitialized",
+            OwnerState::Base { .. } => "Base",
+            OwnerState::Proposed { .. } => "Proposed",
+            OwnerState::AbolitionProposed { .. } => "AbolitionProposed",
+            OwnerState::Abolished => "Abolished",
+            OwnerState::MultiOwner(_) => "MultiOwner",
+        }
     }
 
-    //--------------------------------------------------------------------------------------------------
-    // Queries
-    //--------------------------------------------------------------------------------------------------
-    pub fn current(&self, storage: &'a dyn Storage) -> StdResult<Option<Addr>> {
-        Ok(match self.state(storage)? {
-            OwnerState::Base { owner, .. } => Some(owner),
-            OwnerState::Proposed { owner, .. } => Some(owner),
+    pub(crate) fn owner(&self) -> Option<Addr> {
+        match self {
+            OwnerState::Base { owner, .. } => Some(owner.clone()),
+            OwnerState::Proposed { owner, .. } => Some(owner.clone()),
+            OwnerState::AbolitionProposed { owner, .. } => Some(owner.clone()),
             _ => None,
-        })
+        }
     }
 
-    pub fn is_owner(&self, storage: &'a dyn Storage, addr: &Addr) -> StdResult<bool> {
-        match self.current(storage)? {
-            Some(owner) if owner == addr => Ok(true),
-            _ => Ok(false),
+    pub(crate) fn proposed(&self) -> Option<Addr> {
+        match self {
+            OwnerState::Proposed { proposed, .. } => Some(proposed.clone()),
+            OwnerState::MultiOwner(multi) => multi.proposed.clone(),
+            _ => None,
         }
     }
 
-    pub fn proposed(&self, storage: &'a dyn Storage) -> StdResult<Option<Addr>> {
-        Ok(match self.state(storage)? {
-            OwnerState::Proposed { proposed, .. } => Some(proposed),
+    pub(crate) fn proposed_expiry(&self) -> Option<Expiration> {
+        match self {
+            OwnerState::Proposed { expiry, .. } => *expiry,
+            OwnerState::MultiOwner(multi) => multi.expiry,
             _ => None,
-        })
+        }
     }
 
-    pub fn is_proposed(&self, storage: &'a dyn Storage, addr: &Addr) -> StdResult<bool> {
-        match self.proposed(storage)? {
-            Some(proposed) if proposed == addr => Ok(true),
-            _ => Ok(false),
+    pub(crate) fn accept_after(&self, policy: &OwnerPolicy) -> Option<Timestamp> {
+        match self {
+            OwnerState::Proposed { proposed_at, .. } => {
+                Some(proposed_at.plus_seconds(policy.accept_delay))
+            }
+            OwnerState::MultiOwner(MultiOwnerState {
+                proposed: Some(_),
+                proposed_at,
+                ..
+            }) => Some(proposed_at.plus_seconds(policy.accept_delay)),
+            _ => None,
         }
     }
 
     #[cfg(feature = "emergency-owner")]
-    pub fn emergency_owner(&self, storage: &'a dyn Storage) -> StdResult<Option<Addr>> {
-        Ok(match self.state(storage)? {
+    pub(crate) fn emergency_owner(&self) -> Option<Addr> {
+        match self {
             OwnerState::Base {
                 emergency_owner, ..
-            } => emergency_owner,
+            } => emergency_owner.clone(),
             OwnerState::Proposed {
                 emergency_owner, ..
-            } => emergency_owner,
+            } => emergency_owner.clone(),
+            OwnerState::AbolitionProposed {
+                emergency_owner, ..
+            } => emergency_owner.clone(),
+            OwnerState::MultiOwner(multi) => multi.emergency_owner.clone(),
             _ => None,
-        })
+        }
     }
 
-    #[cfg(feature = "emergency-owner")]
-    pub fn is_emergency_owner(&self, storage: &'a dyn Storage, addr: &Addr) -> StdResult<bool> {
-        match self.emergency_owner(storage)? {
-            Some(em_owner) if em_owner == addr => Ok(true),
+    pub(crate) fn owners(&self) -> Vec<Addr> {
+        match self {
+            OwnerState::MultiOwner(multi) => multi.owners.clone(),
+            state => state.owner().into_iter().collect(),
+        }
+    }
+
+    pub(crate) fn abolish_after(&self) -> Option<Timestamp> {
+        match self {
+            OwnerState::AbolitionProposed { abolish_after, .. } => Some(*abolish_after),
+            OwnerState::MultiOwner(multi) => multi.abolish_after,
+            _ => None,
+        }
+    }
+
+    /// The approvals needed in multi-owner mode, None otherwise
+    pub(crate) fn threshold(&self) -> Option<u32> {
+        match self {
+            OwnerState::MultiOwner(multi) => Some(multi.threshold),
+            _ => None,
+        }
+    }
+
+    /// True if the update was recorded as the sender's approval without reaching the threshold.
+    /// Approvals are dropped once the threshold is reached, so only those below it remain.
+    pub(crate) fn awaits_approval(&self, sender: &Addr, update: &OwnerUpdate) -> StdResult<bool> {
+        match self {
+            OwnerState::MultiOwner(multi) => {
+                let action = String::from_utf8(to_vec(update)?).map_err(StdError::from)?;
+                Ok(multi
+                    .pending
+                    .iter()
+                    .any(|p| p.action == action && p.approvals.contains(sender)))
+            }
             _ => Ok(false),
         }
     }
 
-    pub fn query(&self, storage: &'a dyn Storage) -> StdResult<OwnerResponse> {
-        Ok(OwnerResponse {
-            owner: self.current(storage)?.map(Into::into),
-            proposed: self.proposed(storage)?.map(Into::into),
-            initialized: !matches!(self.state(storage)?, OwnerState::Uninitialized),
-            abolished: matches!(self.state(storage)?, OwnerState::Abolished),
+    /// Whether anything but the collected approvals differs between the two states
+    pub(crate) fn changed_from(&self, previous: &OwnerState) -> bool {
+        fn without_approvals(state: &OwnerState) -> OwnerState {
+            match state.clone() {
+                OwnerState::MultiOwner(mut multi) => {
+                    multi.pending.clear();
+                    OwnerState::MultiOwner(multi)
+                }
+                state => state,
+            }
+        }
+        without_approvals(self) != without_approvals(previous)
+    }
+
+    fn multi_owner(&self) -> Option<MultiOwnerResponse> {
+        match self {
+            OwnerState::MultiOwner(multi) => Some(MultiOwnerResponse {
+                owners: multi.owners.iter().map(Into::into).collect(),
+                threshold: multi.threshold,
+                pending: multi.pending.clone(),
+            }),
+            _ => None,
+        }
+    }
+
+    pub(crate) fn response(&self, label: &str, policy: &OwnerPolicy) -> OwnerResponse {
+        OwnerResponse {
+            label: label.to_string(),
+            owner: self.owner().map(Into::into),
+            proposed: self.proposed().map(Into::into),
+            proposed_expiry: self.proposed_expiry(),
+            accept_after: self.accept_after(policy),
+            initialized: !matches!(self, OwnerState::Uninitialized),
+            abolished: matches!(self, OwnerState::Abolished),
+            abolish_after: self.abolish_after(),
             #[cfg(feature = "emergency-owner")]
-            emergency_owner: self.emergency_owner(storage)?.map(Into::into),
-        })
+            emergency_owner: self.emergency_owner().map(Into::into),
+            multi_owner: self.multi_owner(),
+        }
     }
 
-    //--------------------------------------------------------------------------------------------------
-    // Mutations
-    //--------------------------------------------------------------------------------------------------
-    /// Execute inside instantiate fn
-    pub fn initialize(
-        &self,
-        storage: &'a mut dyn Storage,
-        api: &'a dyn Api,
-        init_action: OwnerInit,
-    ) -> OwnerResult<()> {
-        let initial_state = self.state(storage)?;
-        match initial_state {
-            OwnerState::Uninitialized => {
-                let new_state = match init_action {
-                    OwnerInit::SetInitialOwner { owner } => {
-                        let validated = api.addr_validate(&owner)?;
-                        OwnerState::Base {
-                            owner: validated,
-                            #[cfg(feature = "emergency-owner")]
-                            emergency_owner: None,
-                        }
-                    }
-                    OwnerInit::AbolishOwnerRole => OwnerState::Abolished,
-                };
-                self.0.save(storage, &new_state)?;
-                Ok(())
-            }
-            // Can only be in uninitialized state to call this fn
-            _ => Err(OwnerError::StateTransitionError {}),
+    pub(crate) fn assert_owner(&self, caller: &Addr) -> OwnerResult<()> {
+        if self.owner().as_ref() != Some(caller) {
+            Err(OwnerError::not_owner(&self.owners(), caller))
+        } else {
+            Ok(())
         }
     }
 
-    /// Composes execute responses for owner state updates
-    pub fn update<C, Q: CustomQuery>(
-        &self,
-        deps: DepsMut<Q>,
-        info: MessageInfo,
-        update: OwnerUpdate,
-    ) -> OwnerResult<Response<C>>
-    where
-        C: Clone + Debug + PartialEq + JsonSchema,
-    {
-        let new_state = self.transition_state(deps.storage, deps.api, &info.sender, update)?;
-        self.0.save(deps.storage, &new_state)?;
+    pub(crate) fn assert_proposed(&self, caller: &Addr) -> OwnerResult<()> {
+        if self.proposed().as_ref() != Some(caller) {
+            Err(OwnerError::not_proposed_owner(self.proposed(), caller))
+        } else {
+            Ok(())
+        }
+    }
 
-        let res = self.query(deps.storage)?;
-        Ok(Response::new()
-            .add_attribute("action", "update_owner")
-            .add_attribute("owner", res.owner.unwrap_or_else(|| "None".to_string()))
-            .add_attribute(
-                "proposed",
-                res.proposed.unwrap_or_else(|| "None".to_string()),
-            )
-            .add_attribute("sender", info.sender))
+    #[cfg(feature = "emergency-owner")]
+    pub(crate) fn assert_emergency_owner(&self, caller: &Addr) -> OwnerResult<()> {
+        if self.emergency_owner().as_ref() != Some(caller) {
+            Err(OwnerError::NotEmergencyOwner {})
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Records an approval of a contract-defined action in multi-owner mode, see
+    /// Owner.assert_owner_threshold(). Returns the updated state, if any, and whether the
+    /// threshold has been reached.
+    pub(crate) fn approve(
+        self,
+        caller: &Addr,
+        action: &str,
+    ) -> OwnerResult<(Option<OwnerState>, bool)> {
+        match self {
+            OwnerState::MultiOwner(mut multi) => {
+                let reached = multi.approve(caller, action.to_string())?;
+                Ok((Some(OwnerState::MultiOwner(multi)), reached))
+            }
+            state => {
+                state.assert_owner(caller)?;
+                Ok((None, true))
+            }
+        }
     }
 
     /// Executes owner state transitions
-    fn transition_state(
-        &self,
-        storage: &'a mut dyn Storage,
-        api: &'a dyn Api,
+    pub(crate) fn transition(
+        self,
+        policy: &OwnerPolicy,
+        api: &dyn Api,
+        block: &BlockInfo,
         sender: &Addr,
         event: OwnerUpdate,
     ) -> OwnerResult<OwnerState> {
-        let state = self.state(storage)?;
+        let invalid = OwnerError::state_transition(self.name(), event.name());
+        let current = self.clone();
 
-        let new_state = match (state, event) {
+        let new_state = match (self, event) {
             (
                 OwnerState::Base {
                     owner,
@@ -240,13 +444,16 @@ impl<'a> Owner<'a> {
                     emergency_owner,
                     ..
                 },
-                OwnerUpdate::ProposeNewOwner { proposed },
+                OwnerUpdate::ProposeNewOwner { proposed, expiry },
             ) => {
-                self.assert_owner(storage, sender)?;
+                current.assert_owner(sender)?;
                 let validated = api.addr_validate(&proposed)?;
+                policy.validate_expiry(block, expiry)?;
                 OwnerState::Proposed {
                     owner,
                     proposed: validated,
+                    expiry,
+                    proposed_at: block.time,
                     #[cfg(feature = "emergency-owner")]
                     emergency_owner,
                 }
@@ -256,7 +463,7 @@ impl<'a> Owner<'a> {
                 OwnerState::Base { owner, .. },
                 OwnerUpdate::SetEmergencyOwner { emergency_owner },
             ) => {
-                self.assert_owner(storage, sender)?;
+                current.assert_owner(sender)?;
                 let validated = api.addr_validate(&emergency_owner)?;
                 OwnerState::Base {
                     owner,
@@ -265,372 +472,4310 @@ impl<'a> Owner<'a> {
             }
             #[cfg(feature = "emergency-owner")]
             (OwnerState::Base { owner, .. }, OwnerUpdate::ClearEmergencyOwner) => {
-                self.assert_owner(storage, sender)?;
+                current.assert_owner(sender)?;
                 OwnerState::Base {
                     owner,
                     emergency_owner: None,
                 }
             }
-            (OwnerState::Base { .. }, OwnerUpdate::AbolishOwnerRole) => {
-                self.assert_owner(storage, sender)?;
-                OwnerState::Abolished
+            #[cfg(feature = "emergency-owner")]
+            (
+                OwnerState::Proposed {
+                    owner,
+                    proposed,
+                    expiry,
+                    proposed_at,
+                    ..
+                },
+                OwnerUpdate::SetEmergencyOwner { emergency_owner },
+            ) => {
+                current.assert_owner(sender)?;
+                let validated = api.addr_validate(&emergency_owner)?;
+                OwnerState::Proposed {
+                    owner,
+                    proposed,
+                    expiry,
+                    proposed_at,
+                    emergency_owner: Some(validated),
+                }
             }
+            #[cfg(feature = "emergency-owner")]
             (
                 OwnerState::Proposed {
+                    owner,
                     proposed,
-                    #[cfg(feature = "emergency-owner")]
-                    emergency_owner,
+                    expiry,
+                    proposed_at,
                     ..
                 },
-                OwnerUpdate::AcceptProposed,
+                OwnerUpdate::ClearEmergencyOwner,
             ) => {
-                self.assert_proposed(storage, sender)?;
+                current.assert_owner(sender)?;
+                OwnerState::Proposed {
+                    owner,
+                    proposed,
+                    expiry,
+                    proposed_at,
+                    emergency_owner: None,
+                }
+            }
+            #[cfg(feature = "emergency-owner")]
+            (OwnerState::Base { owner, .. }, OwnerUpdate::ResignEmergencyOwner) => {
+                current.assert_emergency_owner(sender)?;
                 OwnerState::Base {
-                    owner: proposed,
-                    #[cfg(feature = "emergency-owner")]
-                    emergency_owner,
+                    owner,
+                    emergency_owner: None,
                 }
             }
+            #[cfg(feature = "emergency-owner")]
             (
                 OwnerState::Proposed {
                     owner,
-                    #[cfg(feature = "emergency-owner")]
-                    emergency_owner,
+                    proposed,
+                    expiry,
+                    proposed_at,
                     ..
                 },
-                OwnerUpdate::ClearProposed,
+                OwnerUpdate::ResignEmergencyOwner,
             ) => {
-                self.assert_owner(storage, sender)?;
-                OwnerState::Base {
+                current.assert_emergency_owner(sender)?;
+                OwnerState::Proposed {
                     owner,
-                    #[cfg(feature = "emergency-owner")]
-                    emergency_owner,
+                    proposed,
+                    expiry,
+                    proposed_at,
+                    emergency_owner: None,
                 }
             }
-            (_, _) => return Err(OwnerError::StateTransitionError {}),
-        };
-        Ok(new_state)
-    }
-
-    //--------------------------------------------------------------------------------------------------
-    // Assertions
-    //--------------------------------------------------------------------------------------------------
-    /// Similar to is_owner() except it raises an exception if caller is not current owner
-    pub fn assert_owner(&self, storage: &'a dyn Storage, caller: &Addr) -> OwnerResult<()> {
-        if !self.is_owner(storage, caller)? {
-            Err(OwnerError::NotOwner {})
-        } else {
-            Ok(())
-        }
-    }
-
-    pub fn assert_proposed(&self, storage: &'a dyn Storage, caller: &Addr) -> OwnerResult<()> {
-        if !self.is_proposed(storage, caller)? {
-            Err(OwnerError::NotProposedOwner {})
-        } else {
-            Ok(())
-        }
-    }
-
-    #[cfg(feature = "emergency-owner")]
-    pub fn assert_emergency_owner(
-        &self,
-        storage: &'a dyn Storage,
-        caller: &Addr,
-    ) -> OwnerResult<()> {
-        if !self.is_emergency_owner(storage, caller)? {
-            Err(OwnerError::NotEmergencyOwner {})
-        } else {
-            Ok(())
-        }
-    }
-}
+            #[cfg(feature = "emergency-owner")]
+            (
+                OwnerState::AbolitionProposed {
+                    owner,
+                    abolish_after,
+                    ..
+                },
+                OwnerUpdate::SetEmergencyOwner { emergency_owner },
+            ) => {
+                current.assert_owner(sender)?;
+                let validated = api.addr_validate(&emergency_owner)?;
+                OwnerState::AbolitionProposed {
+                    owner,
+                    abolish_after,
+                    emergency_owner: Some(validated),
+                }
+            }
+            #[cfg(feature = "emergency-owner")]
+            (
+                OwnerState::AbolitionProposed {
+                    owner,
+                    abolish_after,
+                    ..
+                },
+                OwnerUpdate::ClearEmergencyOwner,
+            ) => {
+                current.assert_owner(sender)?;
+                OwnerState::AbolitionProposed {
+                    owner,
+                    abolish_after,
+                    emergency_owner: None,
+                }
+            }
+            #[cfg(feature = "emergency-owner")]
+            (
+                OwnerState::AbolitionProposed {
+                    owner,
+                    abolish_after,
+                    ..
+                },
+                OwnerUpdate::ResignEmergencyOwner,
+            ) => {
+                current.assert_emergency_owner(sender)?;
+                OwnerState::AbolitionProposed {
+                    owner,
+                    abolish_after,
+                    emergency_owner: None,
+                }
+            }
+            (
+                OwnerState::Base {
+                    owner,
+                    #[cfg(feature = "emergency-owner")]
+                    emergency_owner,
+                },
+                OwnerUpdate::AbolishOwnerRole,
+            ) => {
+                current.assert_owner(sender)?;
+                OwnerState::AbolitionProposed {
+                    owner,
+                    abolish_after: block.time.plus_seconds(policy.abolition_delay()),
+                    #[cfg(feature = "emergency-owner")]
+                    emergency_owner,
+                }
+            }
+            (
+                OwnerState::AbolitionProposed {
+                    owner,
+                    #[cfg(feature = "emergency-owner")]
+                    emergency_owner,
+                    ..
+                },
+                OwnerUpdate::CancelAbolition,
+            ) => {
+                current.assert_owner(sender)?;
+                OwnerState::Base {
+                    owner,
+                    #[cfg(feature = "emergency-owner")]
+                    emergency_owner,
+                }
+            }
+            (
+                OwnerState::AbolitionProposed { abolish_after, .. },
+                OwnerUpdate::ConfirmAbolition,
+            ) => {
+                current.assert_owner(sender)?;
+                if block.time < abolish_after {
+                    return Err(OwnerError::AbolitionTimelockNotElapsed { abolish_after });
+                }
+                OwnerState::Abolished
+            }
+            (
+                OwnerState::Proposed {
+                    proposed,
+                    expiry,
+                    proposed_at,
+                    #[cfg(feature = "emergency-owner")]
+                    emergency_owner,
+                    ..
+                },
+                OwnerUpdate::AcceptProposed,
+            ) => {
+                current.assert_proposed(sender)?;
+                policy.assert_acceptable(block, expiry, proposed_at)?;
+                OwnerState::Base {
+                    owner: proposed,
+                    #[cfg(feature = "emergency-owner")]
+                    emergency_owner,
+                }
+            }
+            (
+                OwnerState::Proposed {
+                    owner,
+                    #[cfg(feature = "emergency-owner")]
+                    emergency_owner,
+                    ..
+                },
+                OwnerUpdate::ClearProposed,
+            ) => {
+                current.assert_owner(sender)?;
+                OwnerState::Base {
+                    owner,
+                    #[cfg(feature = "emergency-owner")]
+                    emergency_owner,
+                }
+            }
+            (OwnerState::MultiOwner(multi), event) => OwnerState::transition_multi_owner(
+                &current, multi, policy, api, block, sender, event,
+            )?,
+            (_, _) => return Err(invalid),
+        };
+        Ok(new_state)
+    }
+
+    /// Executes owner state transitions while the role is held by a group of owners
+    fn transition_multi_owner(
+        current: &OwnerState,
+        mut multi: MultiOwnerState,
+        policy: &OwnerPolicy,
+        api: &dyn Api,
+        block: &BlockInfo,
+        sender: &Addr,
+        event: OwnerUpdate,
+    ) -> OwnerResult<OwnerState> {
+        let action = String::from_utf8(to_vec(&event)?).map_err(StdError::from)?;
+        let invalid = OwnerError::state_transition("MultiOwner", event.name());
+
+        let new_state = match (multi.proposed.clone(), multi.abolish_after, event) {
+            (None, None, OwnerUpdate::ProposeNewOwner { proposed, expiry }) => {
+                let validated = api.addr_validate(&proposed)?;
+                policy.validate_expiry(block, expiry)?;
+                if multi.approve(sender, action)? {
+                    multi.proposed = Some(validated);
+                    multi.expiry = expiry;
+                    multi.proposed_at = block.time;
+                    multi.clear_owner_updates();
+                }
+                OwnerState::MultiOwner(multi)
+            }
+            (None, None, OwnerUpdate::AbolishOwnerRole) => {
+                if multi.approve(sender, action)? {
+                    multi.abolish_after = Some(block.time.plus_seconds(policy.abolition_delay()));
+                    multi.clear_owner_updates();
+                }
+                OwnerState::MultiOwner(multi)
+            }
+            // Like clearing a proposal, calling off abolition only needs a single owner
+            (_, Some(_), OwnerUpdate::CancelAbolition) => {
+                multi.assert_member(sender)?;
+                multi.abolish_after = None;
+                multi.clear_owner_updates();
+                OwnerState::MultiOwner(multi)
+            }
+            (_, Some(abolish_after), OwnerUpdate::ConfirmAbolition) => {
+                multi.assert_member(sender)?;
+                if block.time < abolish_after {
+                    return Err(OwnerError::AbolitionTimelockNotElapsed { abolish_after });
+                }
+                OwnerState::Abolished
+            }
+            #[cfg(feature = "emergency-owner")]
+            (_, _, OwnerUpdate::SetEmergencyOwner { emergency_owner }) => {
+                let validated = api.addr_validate(&emergency_owner)?;
+                if multi.approve(sender, action)? {
+                    multi.emergency_owner = Some(validated);
+                    multi.clear_owner_updates();
+                }
+                OwnerState::MultiOwner(multi)
+            }
+            #[cfg(feature = "emergency-owner")]
+            (_, _, OwnerUpdate::ClearEmergencyOwner) => {
+                if multi.approve(sender, action)? {
+                    multi.emergency_owner = None;
+                    multi.clear_owner_updates();
+                }
+                OwnerState::MultiOwner(multi)
+            }
+            #[cfg(feature = "emergency-owner")]
+            (_, _, OwnerUpdate::ResignEmergencyOwner) => {
+                current.assert_emergency_owner(sender)?;
+                multi.emergency_owner = None;
+                multi.clear_owner_updates();
+                OwnerState::MultiOwner(multi)
+            }
+            (Some(proposed), _, OwnerUpdate::AcceptProposed) => {
+                current.assert_proposed(sender)?;
+                policy.assert_acceptable(block, multi.expiry, multi.proposed_at)?;
+                OwnerState::Base {
+                    owner: proposed,
+                    #[cfg(feature = "emergency-owner")]
+                    emergency_owner: multi.emergency_owner,
+                }
+            }
+            // Backing out of a handover is not privileged, any single owner can do it
+            (Some(_), _, OwnerUpdate::ClearProposed) => {
+                multi.assert_member(sender)?;
+                multi.proposed = None;
+                multi.expiry = None;
+                multi.clear_owner_updates();
+                OwnerState::MultiOwner(multi)
+            }
+            (_, _, OwnerUpdate::WithdrawApproval { action }) => {
+                multi.withdraw(sender, &action)?;
+                OwnerState::MultiOwner(multi)
+            }
+            (_, _, _) => return Err(invalid),
+        };
+        Ok(new_state)
+    }
+}
+
+impl MultiOwnerState {
+    fn assert_member(&self, addr: &Addr) -> OwnerResult<()> {
+        if !self.owners.contains(addr) {
+            Err(OwnerError::not_owner(&self.owners, addr))
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Records the sender's approval of the action. Returns true once the threshold has been
+    /// reached, in which case the collected approvals are discarded.
+    fn approve(&mut self, sender: &Addr, action: String) -> OwnerResult<bool> {
+        self.assert_member(sender)?;
+        let approved = self
+            .pending
+            .iter()
+            .filter(|p| p.approvals.contains(sender))
+            .count();
+        let idx = match self.pending.iter().position(|p| p.action == action) {
+            Some(idx) if self.pending[idx].approvals.contains(sender) => {
+                return Err(OwnerError::AlreadyApproved {});
+            }
+            _ if approved as u32 >= MAX_PENDING_APPROVALS => {
+                return Err(OwnerError::TooManyPendingApprovals {
+                    max: MAX_PENDING_APPROVALS,
+                });
+            }
+            Some(idx) => idx,
+            None => {
+                self.pending.push(PendingApproval {
+                    action,
+                    approvals: vec![],
+                });
+                self.pending.len() - 1
+            }
+        };
+
+        let pending = &mut self.pending[idx];
+        pending.approvals.push(sender.clone());
+
+        if pending.approvals.len() as u32 >= self.threshold {
+            self.pending.remove(idx);
+            Ok(true)
+        } else {
+            Ok(false)
+        }
+    }
+
+    /// Takes back the sender's approval of the action, dropping the action once nobody approves it
+    fn withdraw(&mut self, sender: &Addr, action: &str) -> OwnerResult<()> {
+        self.assert_member(sender)?;
+        let idx = self
+            .pending
+            .iter()
+            .position(|p| p.action == action && p.approvals.contains(sender))
+            .ok_or(OwnerError::ApprovalNotFound {})?;
+
+        self.pending[idx].approvals.retain(|addr| addr != sender);
+        if self.pending[idx].approvals.is_empty() {
+            self.pending.remove(idx);
+        }
+        Ok(())
+    }
+
+    /// Discards the approvals of owner updates once the state has changed, as they were given for
+    /// the state before. Approvals of contract-defined actions are kept.
+    fn clear_owner_updates(&mut self) {
+        self.pending
+            .retain(|p| from_slice::<OwnerUpdate>(p.action.as_bytes()).is_err());
+    }
+}
+
+#[cw_serde]
+pub enum OwnerUpdate {
+    /// Proposes a new owner to take role. Only current owner can execute.
+    /// Optionally, the proposal can no longer be accepted once the expiry has passed. With an
+    /// acceptance timelock, the expiry has to be a time after the timelock ends.
+    ProposeNewOwner {
+        proposed: String,
+        expiry: Option<Expiration>,
+    },
+    /// Clears the currently proposed owner. Only current owner can execute.
+    ClearProposed,
+    /// Promotes the proposed owner to be the current one. Only the proposed owner can execute.
+    AcceptProposed,
+    /// Announces that the Owner role will be thrown away forever. Only current owner can execute.
+    /// Takes effect once confirmed after the cooling-off period (see ABOLITION_DELAY).
+    AbolishOwnerRole,
+    /// Calls off a pending abolition of the Owner role. Only current owner can execute.
+    CancelAbolition,
+    /// Throws away the keys to the Owner role forever once the cooling-off period has passed.
+    /// Once done, no owner can ever be set later. Only current owner can execute.
+    ConfirmAbolition,
+    #[cfg(feature = "emergency-owner")]
+    /// A separate entity managed by Owner that can be used for granting specific emergency powers.
+    SetEmergencyOwner { emergency_owner: String },
+    #[cfg(feature = "emergency-owner")]
+    /// Remove the entity in the Emergency Owner role
+    ClearEmergencyOwner,
+    #[cfg(feature = "emergency-owner")]
+    /// Gives up the Emergency Owner role. Only the current emergency owner can execute.
+    ResignEmergencyOwner,
+    /// Takes back the caller's approval of a pending multi-owner action, as listed in
+    /// MultiOwnerResponse.pending. Only an owner who approved the action can execute.
+    WithdrawApproval { action: String },
+}
+
+impl OwnerUpdate {
+    /// The snake_case name of the variant, as it appears in the serialized message
+    pub(crate) fn name(&self) -> &'static str {
+        match self {
+            OwnerUpdate::ProposeNewOwner { .. } => "propose_new_owner",
+            OwnerUpdate::ClearProposed => "clear_proposed",
+            OwnerUpdate::AcceptProposed => "accept_proposed",
+            OwnerUpdate::AbolishOwnerRole => "abolish_owner_role",
+            OwnerUpdate::CancelAbolition => "cancel_abolition",
+            OwnerUpdate::ConfirmAbolition => "confirm_abolition",
+            #[cfg(feature = "emergency-owner")]
+            OwnerUpdate::SetEmergencyOwner { .. } => "set_emergency_owner",
+            #[cfg(feature = "emergency-owner")]
+            OwnerUpdate::ClearEmergencyOwner => "clear_emergency_owner",
+            #[cfg(feature = "emergency-owner")]
+            OwnerUpdate::ResignEmergencyOwner => "resign_emergency_owner",
+            OwnerUpdate::WithdrawApproval { .. } => "withdraw_approval",
+        }
+    }
+
+    /// Whether the emergency owner is giving up the role, which gets a response of its own
+    pub(crate) fn is_resignation(&self) -> bool {
+        #[cfg(feature = "emergency-owner")]
+        if matches!(self, OwnerUpdate::ResignEmergencyOwner) {
+            return true;
+        }
+        false
+    }
+}
+
+/// The execute message of cw-ownable (`cw_ownable::Action`). Lets migrated contracts keep
+/// accepting it by converting it into the matching OwnerUpdate.
+#[cw_serde]
+pub enum CwOwnableAction {
+    TransferOwnership {
+        new_owner: String,
+        expiry: Option<Expiration>,
+    },
+    AcceptOwnership,
+    RenounceOwnership,
+}
+
+impl From<CwOwnableAction> for OwnerUpdate {
+    fn from(action: CwOwnableAction) -> Self {
+        match action {
+            CwOwnableAction::TransferOwnership { new_owner, expiry } => {
+                OwnerUpdate::ProposeNewOwner {
+                    proposed: new_owner,
+                    expiry,
+                }
+            }
+            CwOwnableAction::AcceptOwnership => OwnerUpdate::AcceptProposed,
+            // Unlike in cw-ownable, this only announces abolition and still needs confirming
+            CwOwnableAction::RenounceOwnership => OwnerUpdate::AbolishOwnerRole,
+        }
+    }
+}
+
+/// Storage key used by cw-ownable
+const CW_OWNABLE_KEY: &str = "ownership";
+
+/// Storage layout of cw-ownable's `Ownership<Addr>`
+#[cw_serde]
+pub struct CwOwnableOwnership {
+    pub owner: Option<Addr>,
+    pub pending_owner: Option<Addr>,
+    pub pending_expiry: Option<Expiration>,
+}
+
+#[cw_serde]
+pub enum OwnerInit {
+    /// Sets the initial owner when none. No restrictions permissions to modify.
+    SetInitialOwner { owner: String },
+    /// Sets the initial owner and the minimum delay (in seconds) between proposing a new owner
+    /// and that owner being able to accept the role.
+    SetInitialOwnerWithTimelock { owner: String, accept_delay: u64 },
+    /// Hands the owner role to a group of addresses. Proposing a new owner, announcing abolition
+    /// of the role and managing the emergency owner then require approvals from `threshold` of them.
+    /// Any single one of them can clear a proposal or call off abolition, which gives each owner a
+    /// veto over handovers and abolition.
+    ///
+    /// No single address is the owner in this mode, so assert_owner(), authorize_owner(),
+    /// #[only_owner] and OwnerMap assertions never pass. Use assert_owner_threshold() instead.
+    SetInitialOwners { owners: Vec<String>, threshold: u32 },
+    /// Throws away the keys to the Owner role forever. Once done, no owner can ever be set later.
+    AbolishOwnerRole,
+}
+
+impl OwnerInit {
+    /// The snake_case name of the variant, as it appears in the serialized message
+    pub(crate) fn name(&self) -> &'static str {
+        match self {
+            OwnerInit::SetInitialOwner { .. } => "set_initial_owner",
+            OwnerInit::SetInitialOwnerWithTimelock { .. } => "set_initial_owner_with_timelock",
+            OwnerInit::SetInitialOwners { .. } => "set_initial_owners",
+            OwnerInit::AbolishOwnerRole => "abolish_owner_role",
+        }
+    }
+
+    /// The state to start in, along with the policy to store if any
+    pub(crate) fn into_state(
+        self,
+        api: &dyn Api,
+    ) -> OwnerResult<(OwnerState, Option<OwnerPolicy>)> {
+        let mut policy = None;
+        let new_state = match self {
+            OwnerInit::SetInitialOwner { owner } => {
+                let validated = api.addr_validate(&owner)?;
+                OwnerState::Base {
+                    owner: validated,
+                    #[cfg(feature = "emergency-owner")]
+                    emergency_owner: None,
+                }
+            }
+            OwnerInit::SetInitialOwnerWithTimelock {
+                owner,
+                accept_delay,
+            } => {
+                let validated = api.addr_validate(&owner)?;
+                policy = Some(OwnerPolicy { accept_delay });
+                OwnerState::Base {
+                    owner: validated,
+                    #[cfg(feature = "emergency-owner")]
+                    emergency_owner: None,
+                }
+            }
+            OwnerInit::SetInitialOwners { owners, threshold } => {
+                let mut validated = Vec::with_capacity(owners.len());
+                for owner in owners {
+                    let owner = api.addr_validate(&owner)?;
+                    if validated.contains(&owner) {
+                        return Err(OwnerError::DuplicateOwner {});
+                    }
+                    validated.push(owner);
+                }
+                if threshold == 0 || threshold as usize > validated.len() {
+                    return Err(OwnerError::InvalidThreshold {});
+                }
+                OwnerState::MultiOwner(MultiOwnerState {
+                    owners: validated,
+                    threshold,
+                    pending: vec![],
+                    proposed: None,
+                    expiry: None,
+                    proposed_at: Timestamp::default(),
+                    abolish_after: None,
+                    #[cfg(feature = "emergency-owner")]
+                    emergency_owner: None,
+                })
+            }
+            OwnerInit::AbolishOwnerRole => OwnerState::Abolished,
+        };
+        Ok((new_state, policy))
+    }
+}
+
+/// Optional rules applied on top of the state machine. Set once during initialization.
+#[cw_serde]
+#[derive(Default)]
+pub(crate) struct OwnerPolicy {
+    /// Seconds that must pass after a proposal before the proposed owner can accept it
+    accept_delay: u64,
+}
+
+impl OwnerPolicy {
+    fn abolition_delay(&self) -> u64 {
+        self.accept_delay.max(ABOLITION_DELAY)
+    }
+
+    fn validate_expiry(&self, block: &BlockInfo, expiry: Option<Expiration>) -> OwnerResult<()> {
+        let accept_after = block.time.plus_seconds(self.accept_delay);
+        match expiry {
+            Some(e) if e.is_expired(block) => Err(OwnerError::InvalidExpiry {}),
+            // Proposal would expire before it could ever be accepted
+            Some(Expiration::AtTime(t)) if t <= accept_after => Err(OwnerError::InvalidExpiry {}),
+            // Heights can't be checked against the timelock, which is counted in seconds
+            Some(Expiration::AtHeight(_)) if self.accept_delay > 0 => {
+                Err(OwnerError::InvalidExpiryHeight {})
+            }
+            _ => Ok(()),
+        }
+    }
+
+    fn assert_acceptable(
+        &self,
+        block: &BlockInfo,
+        expiry: Option<Expiration>,
+        proposed_at: Timestamp,
+    ) -> OwnerResult<()> {
+        if expiry.is_some_and(|e| e.is_expired(block)) {
+            return Err(OwnerError::ProposalExpired {});
+        }
+        let accept_after = proposed_at.plus_seconds(self.accept_delay);
+        if block.time < accept_after {
+            return Err(OwnerError::TimelockNotElapsed { accept_after });
+        }
+        Ok(())
+    }
+}
+
+/// Composes the execute response for a successful owner update
+pub(crate) fn update_response<C>(
+    label: &str,
+    update: &OwnerUpdate,
+    event: OwnerEvent,
+) -> Response<C>
+where
+    C: Clone + Debug + PartialEq + JsonSchema,
+{
+    let sender = event.sender.clone();
+    let owner = event.owner.clone();
+    let proposed = event.proposed.clone();
+
+    if update.is_resignation() {
+        return Response::new()
+            .add_event(event.into())
+            .add_attribute("action", "resign_emergency_owner")
+            .add_attribute("label", label)
+            .add_attribute("sender", sender);
+    }
+
+    Response::new()
+        .add_event(event.into())
+        .add_attribute("action", "update_owner")
+        .add_attribute("label", label)
+        .add_attribute(
+            "owner",
+            owner.map_or_else(|| "None".to_string(), Into::into),
+        )
+        .add_attribute(
+            "proposed",
+            proposed.map_or_else(|| "None".to_string(), Into::into),
+        )
+        .add_attribute("sender", sender)
+}
+
+/// Response to a multi-owner approval that has yet to reach the threshold. Carries no event, as
+/// nothing but the approvals changed.
+pub(crate) fn pending_approval_response<C>(label: &str, sender: &Addr) -> Response<C>
+where
+    C: Clone + Debug + PartialEq + JsonSchema,
+{
+    Response::new()
+        .add_attribute("action", "update_owner")
+        .add_attribute("label", label)
+        .add_attribute("status", "pending_approval")
+        .add_attribute("sender", sender)
+}
+
+/// A struct designed to help facilitate a two-step transition between contract owners safely.
+/// It implements a finite state machine with dispatched events to manage state transitions.
+/// The valid transitions are listed in TRANSITIONS, see to_mermaid() for a diagram.
+/// `M` is a marker type set through branded(), which ties the OwnerAuth tokens to this instance.
+pub struct Owner<'a, M = ()> {
+    namespace: &'a str,
+    label: Option<&'a str>,
+    state: Item<'a, OwnerState>,
+    history: bool,
+    snapshots: bool,
+    hooks: bool,
+    notify_proposed: bool,
+    brand: PhantomData<M>,
+}
+
+impl<'a> Owner<'a> {
+    pub const fn new(namespace: &'a str) -> Self {
+        Self {
+            namespace,
+            label: None,
+            state: Item::new(namespace),
+            history: false,
+            snapshots: false,
+            hooks: false,
+            notify_proposed: false,
+            brand: PhantomData,
+        }
+    }
+}
+
+impl<'a, M> Owner<'a, M> {
+    /// Tags the Owner with a marker type, e.g. `Owner::new("risk_owner").branded::<RiskOwner>()`.
+    /// Contracts with several Owners should give each its own marker, so privileged functions
+    /// taking `OwnerAuth<RiskOwner>` can't be called with a token of another Owner.
+    pub const fn branded<N>(self) -> Owner<'a, N> {
+        Owner {
+            namespace: self.namespace,
+            label: self.label,
+            state: self.state,
+            history: self.history,
+            snapshots: self.snapshots,
+            hooks: self.hooks,
+            notify_proposed: self.notify_proposed,
+            brand: PhantomData,
+        }
+    }
+
+    /// Sets the label reported in responses and query results instead of the namespace
+    pub const fn with_label(self, label: &'a str) -> Self {
+        Self {
+            label: Some(label),
+            ..self
+        }
+    }
+
+    /// Keeps a log of every successful update, readable through Owner.history(). The log starts
+    /// with the initialization or migration, so use initialize_with_env() instead of initialize().
+    pub const fn with_history(self) -> Self {
+        Self {
+            history: true,
+            ..self
+        }
+    }
+
+    /// Checkpoints the state on every update so it can be looked up at past heights through
+    /// current_at_height(). Heights up to the initialization or migration resolve to the
+    /// uninitialized state, so use initialize_with_env() instead of initialize().
+    pub const fn with_snapshots(self) -> Self {
+        Self {
+            snapshots: true,
+            ..self
+        }
+    }
+
+    /// Notifies the contracts registered through update_hooks() of every successful update
+    pub const fn with_hooks(self) -> Self {
+        Self {
+            hooks: true,
+            ..self
+        }
+    }
+
+    /// Sends OwnershipProposedMsg to the proposed owner once a transfer is proposed, if it is a
+    /// contract. Proposals to any other address go through without the message.
+    pub const fn with_proposal_notifications(self) -> Self {
+        Self {
+            notify_proposed: true,
+            ..self
+        }
+    }
+
+    fn label(&self) -> &'a str {
+        self.label.unwrap_or(self.namespace)
+    }
+
+    fn state(&self, storage: &'a dyn Storage) -> StdResult<OwnerState> {
+        Ok(self
+            .state
+            .may_load(storage)?
+            .unwrap_or(OwnerState::Uninitialized))
+    }
+
+    fn snapshot_keys(&self) -> (String, String) {
+        (
+            format!("{}_checkpoints", self.namespace),
+            format!("{}_changelog", self.namespace),
+        )
+    }
+
+    fn state_at_height(&self, storage: &'a dyn Storage, height: u64) -> StdResult<OwnerState> {
+        if !self.snapshots {
+            return Err(StdError::generic_err("snapshots not enabled"));
+        }
+        let (checkpoints, changelog) = self.snapshot_keys();
+        let snapshot: SnapshotItem<OwnerState> = SnapshotItem::new(
+            self.namespace,
+            &checkpoints,
+            &changelog,
+            Strategy::EveryBlock,
+        );
+        Ok(snapshot
+            .may_load_at_height(storage, height)?
+            .unwrap_or(OwnerState::Uninitialized))
+    }
+
+    /// Same as state.save(), but also records the previous state in the changelog when
+    /// snapshots are enabled
+    fn save_state(
+        &self,
+        storage: &'a mut dyn Storage,
+        state: &OwnerState,
+        height: u64,
+    ) -> StdResult<()> {
+        if !self.snapshots {
+            return self.state.save(storage, state);
+        }
+        let (checkpoints, changelog) = self.snapshot_keys();
+        let snapshot: SnapshotItem<OwnerState> = SnapshotItem::new(
+            self.namespace,
+            &checkpoints,
+            &changelog,
+            Strategy::EveryBlock,
+        );
+        snapshot.save(storage, state, height)
+    }
+
+    fn policy_key(&self) -> String {
+        format!("{}_policy", self.namespace)
+    }
+
+    fn policy(&self, storage: &'a dyn Storage) -> StdResult<OwnerPolicy> {
+        Ok(Item::new(&self.policy_key())
+            .may_load(storage)?
+            .unwrap_or_default())
+    }
+
+    //--------------------------------------------------------------------------------------------------
+    // Queries
+    //--------------------------------------------------------------------------------------------------
+    /// The single owner, None in multi-owner mode (see owners())
+    pub fn current(&self, storage: &'a dyn Storage) -> StdResult<Option<Addr>> {
+        Ok(self.state(storage)?.owner())
+    }
+
+    /// The owner at the beginning of the given block. Fails unless snapshots are enabled, see
+    /// with_snapshots().
+    pub fn current_at_height(
+        &self,
+        storage: &'a dyn Storage,
+        height: u64,
+    ) -> StdResult<Option<Addr>> {
+        Ok(self.state_at_height(storage, height)?.owner())
+    }
+
+    pub fn is_owner(&self, storage: &'a dyn Storage, addr: &Addr) -> StdResult<bool> {
+        match self.current(storage)? {
+            Some(owner) if owner == addr => Ok(true),
+            _ => Ok(false),
+        }
+    }
+
+    pub fn proposed(&self, storage: &'a dyn Storage) -> StdResult<Option<Addr>> {
+        Ok(self.state(storage)?.proposed())
+    }
+
+    pub fn proposed_expiry(&self, storage: &'a dyn Storage) -> StdResult<Option<Expiration>> {
+        Ok(self.state(storage)?.proposed_expiry())
+    }
+
+    pub fn accept_after(&self, storage: &'a dyn Storage) -> StdResult<Option<Timestamp>> {
+        Ok(self.state(storage)?.accept_after(&self.policy(storage)?))
+    }
+
+    pub fn is_proposed(&self, storage: &'a dyn Storage, addr: &Addr) -> StdResult<bool> {
+        match self.proposed(storage)? {
+            Some(proposed) if proposed == addr => Ok(true),
+            _ => Ok(false),
+        }
+    }
+
+    #[cfg(feature = "emergency-owner")]
+    pub fn emergency_owner(&self, storage: &'a dyn Storage) -> StdResult<Option<Addr>> {
+        Ok(self.state(storage)?.emergency_owner())
+    }
+
+    /// The emergency owner at the beginning of the given block. See current_at_height().
+    #[cfg(feature = "emergency-owner")]
+    pub fn emergency_owner_at_height(
+        &self,
+        storage: &'a dyn Storage,
+        height: u64,
+    ) -> StdResult<Option<Addr>> {
+        Ok(self.state_at_height(storage, height)?.emergency_owner())
+    }
+
+    #[cfg(feature = "emergency-owner")]
+    pub fn is_emergency_owner(&self, storage: &'a dyn Storage, addr: &Addr) -> StdResult<bool> {
+        match self.emergency_owner(storage)? {
+            Some(em_owner) if em_owner == addr => Ok(true),
+            _ => Ok(false),
+        }
+    }
+
+    /// Everyone holding the owner role: the single owner, or all members in multi-owner mode
+    pub fn owners(&self, storage: &'a dyn Storage) -> StdResult<Vec<Addr>> {
+        Ok(self.state(storage)?.owners())
+    }
+
+    pub fn abolish_after(&self, storage: &'a dyn Storage) -> StdResult<Option<Timestamp>> {
+        Ok(self.state(storage)?.abolish_after())
+    }
+
+    pub fn multi_owner(&self, storage: &'a dyn Storage) -> StdResult<Option<MultiOwnerResponse>> {
+        Ok(self.state(storage)?.multi_owner())
+    }
+
+    /// Updates recorded so far, oldest first. Always empty unless created with_history()
+    pub fn history(
+        &self,
+        storage: &'a dyn Storage,
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    ) -> StdResult<Vec<OwnerHistoryRecord>> {
+        History::new(self.namespace).range(storage, start_after, limit)
+    }
+
+    /// Contracts notified of owner updates, in the order they were added
+    pub fn hooks(&self, storage: &'a dyn Storage) -> StdResult<Vec<Addr>> {
+        Hooks::new(self.namespace).list(storage)
+    }
+
+    pub fn query_hooks(&self, storage: &'a dyn Storage) -> StdResult<HooksResponse> {
+        Ok(HooksResponse {
+            hooks: self.hooks(storage)?.into_iter().map(Into::into).collect(),
+        })
+    }
+
+    pub fn query(&self, storage: &'a dyn Storage) -> StdResult<OwnerResponse> {
+        Ok(self
+            .state(storage)?
+            .response(self.label(), &self.policy(storage)?))
+    }
+
+    /// Dispatch helper for the Owner {} variant added by #[owner_query]
+    pub fn query_binary(&self, storage: &'a dyn Storage) -> StdResult<Binary> {
+        to_binary(&self.query(storage)?)
+    }
+
+    //--------------------------------------------------------------------------------------------------
+    // Mutations
+    //--------------------------------------------------------------------------------------------------
+    /// Execute inside instantiate fn. Owners created with_history() or with_snapshots() need
+    /// initialize_with_env().
+    pub fn initialize(
+        &self,
+        storage: &'a mut dyn Storage,
+        api: &'a dyn Api,
+        init_action: OwnerInit,
+    ) -> OwnerResult<()> {
+        if self.history || self.snapshots {
+            return Err(OwnerError::EnvRequired {});
+        }
+        let new_state = self.init_state(storage, api, init_action)?;
+        self.state.save(storage, &new_state)?;
+        Ok(())
+    }
+
+    /// Same as initialize(), but also records the initialization in the history, with the
+    /// contract as the sender, and checkpoints it at the current height
+    pub fn initialize_with_env(
+        &self,
+        storage: &'a mut dyn Storage,
+        api: &'a dyn Api,
+        env: &Env,
+        init_action: OwnerInit,
+    ) -> OwnerResult<()> {
+        let action = init_action.name();
+        let new_state = self.init_state(storage, api, init_action)?;
+        self.save_state(storage, &new_state, env.block.height)?;
+        self.record(storage, env, action, &new_state)?;
+        Ok(())
+    }
+
+    /// Checks the owner has not been initialized yet and stores the policy of the init action
+    fn init_state(
+        &self,
+        storage: &'a mut dyn Storage,
+        api: &'a dyn Api,
+        init_action: OwnerInit,
+    ) -> OwnerResult<OwnerState> {
+        let state = self.state(storage)?;
+        // Can only be in uninitialized state to call this fn
+        if !matches!(state, OwnerState::Uninitialized) {
+            return Err(OwnerError::state_transition(
+                state.name(),
+                init_action.name(),
+            ));
+        }
+
+        let (new_state, policy) = init_action.into_state(api)?;
+        if let Some(policy) = policy {
+            Item::new(&self.policy_key()).save(storage, &policy)?;
+        }
+        Ok(new_state)
+    }
+
+    /// Appends the creation of the owner role to the history, if it is kept
+    fn record(
+        &self,
+        storage: &'a mut dyn Storage,
+        env: &Env,
+        action: &str,
+        new_state: &OwnerState,
+    ) -> StdResult<()> {
+        if !self.history {
+            return Ok(());
+        }
+        let event = OwnerEvent::transition(
+            self.namespace,
+            action,
+            &env.contract.address,
+            &self.policy(storage)?,
+            &OwnerState::Uninitialized,
+            new_state,
+        );
+        History::new(self.namespace).append(storage, &env.block, &event)
+    }
+
+    /// Execute inside migrate fn. Adopts an owner stored as a plain `Item<Addr>` under
+    /// `legacy_key`, optionally removing the old entry. The legacy key may be the same as this
+    /// Owner's namespace, in which case the entry is overwritten in place. Contracts keeping the
+    /// owner in a config struct can read it themselves and use initialize() instead.
+    pub fn migrate_from_addr_item(
+        &self,
+        storage: &'a mut dyn Storage,
+        api: &'a dyn Api,
+        env: &Env,
+        legacy_key: &str,
+        remove_legacy: bool,
+    ) -> OwnerResult<()> {
+        // Under the same key, an initialized Owner would otherwise fail to parse as the legacy owner
+        if legacy_key == self.namespace {
+            if let Ok(state) = self.state(storage) {
+                if !matches!(state, OwnerState::Uninitialized) {
+                    return Err(OwnerError::state_transition(state.name(), "migrate"));
+                }
+            }
+        }
+
+        let legacy: Item<Addr> = Item::new(legacy_key);
+        let legacy_owner =
+            legacy
+                .may_load(storage)?
+                .ok_or_else(|| OwnerError::LegacyOwnerNotFound {
+                    key: legacy_key.to_string(),
+                })?;
+
+        let new_state = OwnerState::Base {
+            owner: api.addr_validate(legacy_owner.as_str())?,
+            #[cfg(feature = "emergency-owner")]
+            emergency_owner: None,
+        };
+        self.adopt_legacy_state(storage, env, legacy_key, remove_legacy, new_state)
+    }
+
+    /// Execute inside migrate fn. Adopts the ownership stored by cw-ownable, including a pending
+    /// transfer and its expiry, optionally removing the old entry. A renounced ownership becomes
+    /// an abolished owner role.
+    pub fn migrate_from_cw_ownable(
+        &self,
+        storage: &'a mut dyn Storage,
+        api: &'a dyn Api,
+        env: &Env,
+        remove_legacy: bool,
+    ) -> OwnerResult<()> {
+        let legacy: Item<CwOwnableOwnership> = Item::new(CW_OWNABLE_KEY);
+        let ownership =
+            legacy
+                .may_load(storage)?
+                .ok_or_else(|| OwnerError::LegacyOwnerNotFound {
+                    key: CW_OWNABLE_KEY.to_string(),
+                })?;
+
+        let new_state = match ownership {
+            CwOwnableOwnership {
+                owner: Some(owner),
+                pending_owner: None,
+                ..
+            } => OwnerState::Base {
+                owner: api.addr_validate(owner.as_str())?,
+                #[cfg(feature = "emergency-owner")]
+                emergency_owner: None,
+            },
+            CwOwnableOwnership {
+                owner: Some(owner),
+                pending_owner: Some(pending_owner),
+                pending_expiry,
+            } => OwnerState::Proposed {
+                owner: api.addr_validate(owner.as_str())?,
+                proposed: api.addr_validate(pending_owner.as_str())?,
+                expiry: pending_expiry,
+                // cw-ownable does not record when the transfer was started
+                proposed_at: Timestamp::default(),
+                #[cfg(feature = "emergency-owner")]
+                emergency_owner: None,
+            },
+            CwOwnableOwnership {
+                owner: None,
+                pending_owner: None,
+                ..
+            } => OwnerState::Abolished,
+            // A pending owner without a current owner can't be produced by cw-ownable itself
+            CwOwnableOwnership {
+                owner: None,
+                pending_owner: Some(_),
+                ..
+            } => return Err(OwnerError::InvalidLegacyOwnership {}),
+        };
+        self.adopt_legacy_state(storage, env, CW_OWNABLE_KEY, remove_legacy, new_state)
+    }
+
+    /// Saves a state converted from legacy storage and records it in the history as "migrate".
+    /// The legacy key may be the same as this Owner's namespace, in which case the entry is
+    /// overwritten in place.
+    fn adopt_legacy_state(
+        &self,
+        storage: &'a mut dyn Storage,
+        env: &Env,
+        legacy_key: &str,
+        remove_legacy: bool,
+        new_state: OwnerState,
+    ) -> OwnerResult<()> {
+        if legacy_key != self.namespace {
+            // Can only be in uninitialized state to adopt a legacy owner
+            let state = self.state(storage)?;
+            if !matches!(state, OwnerState::Uninitialized) {
+                return Err(OwnerError::state_transition(state.name(), "migrate"));
+            }
+            if remove_legacy {
+                storage.remove(legacy_key.as_bytes());
+            }
+        } else {
+            // Snapshots would otherwise read the legacy entry as the previous state
+            storage.remove(legacy_key.as_bytes());
+        }
+        self.save_state(storage, &new_state, env.block.height)?;
+        self.record(storage, env, "migrate", &new_state)?;
+        Ok(())
+    }
+
+    /// Composes execute responses for owner state updates
+    pub fn update<C, Q: CustomQuery>(
+        &self,
+        deps: DepsMut<Q>,
+        env: Env,
+        info: MessageInfo,
+        update: OwnerUpdate,
+    ) -> OwnerResult<Response<C>>
+    where
+        C: Clone + Debug + PartialEq + JsonSchema,
+    {
+        let action = update.name();
+        let previous = self.state(deps.storage)?;
+        let policy = self.policy(deps.storage)?;
+
+        let new_state = previous.clone().transition(
+            &policy,
+            deps.api,
+            &env.block,
+            &info.sender,
+            update.clone(),
+        )?;
+        self.save_state(deps.storage, &new_state, env.block.height)?;
+
+        // In multi-owner mode this only goes through once enough owners approved the update
+        if new_state.awaits_approval(&info.sender, &update)? {
+            return Ok(pending_approval_response(self.label(), &info.sender));
+        }
+
+        let event = OwnerEvent::transition(
+            self.namespace,
+            action,
+            &info.sender,
+            &policy,
+            &previous,
+            &new_state,
+        );
+
+        // Withdrawn approvals leave the owners as they were, so neither is recorded nor announced
+        let changed = new_state.changed_from(&previous);
+        if self.history && changed {
+            History::new(self.namespace).append(deps.storage, &env.block, &event)?;
+        }
+
+        // A failing hook reverts the update, so broken hooks have to be removed by the owner
+        let hook_msgs = if self.hooks && changed {
+            Hooks::new(self.namespace).messages(deps.storage, OwnerChangedHook::from(&event))?
+        } else {
+            vec![]
+        };
+
+        // Multi-owner proposals are only announced once enough owners approved them. Executing an
+        // account would fail, so only contracts are messaged.
+        let notify_msg = match (previous.proposed(), new_state.proposed()) {
+            (None, Some(proposed))
+                if self.notify_proposed
+                    && deps.querier.query_wasm_contract_info(&proposed).is_ok() =>
+            {
+                let msg = OwnershipProposedMsg::OwnershipProposed(OwnershipProposed {
+                    namespace: self.namespace.to_string(),
+                    from: info.sender.clone(),
+                });
+                Some(msg.into_wasm_msg(&proposed)?)
+            }
+            _ => None,
+        };
+
+        Ok(update_response(self.label(), &update, event)
+            .add_messages(hook_msgs)
+            .add_messages(notify_msg))
+    }
+
+    /// Dispatch helper for the UpdateOwner variant added by #[owner_execute]. Same as update()
+    /// except the error is converted into the contract's own error type.
+    pub fn execute_update<C, Q: CustomQuery, E: From<OwnerError>>(
+        &self,
+        deps: DepsMut<Q>,
+        env: Env,
+        info: MessageInfo,
+        update: OwnerUpdate,
+    ) -> Result<Response<C>, E>
+    where
+        C: Clone + Debug + PartialEq + JsonSchema,
+    {
+        self.update(deps, env, info, update).map_err(Into::into)
+    }
+
+    /// Composes execute responses for changes to the registered hooks. Requires with_hooks().
+    pub fn update_hooks<C, Q: CustomQuery>(
+        &self,
+        deps: DepsMut<Q>,
+        info: MessageInfo,
+        update: OwnerHooksUpdate,
+    ) -> OwnerResult<Response<C>>
+    where
+        C: Clone + Debug + PartialEq + JsonSchema,
+    {
+        if !self.hooks {
+            return Err(OwnerError::HooksDisabled {});
+        }
+
+        // In multi-owner mode this only goes through once enough owners approved the update. The
+        // prefix keeps these approvals apart from those of other contract-defined actions.
+        let json = String::from_utf8(to_vec(&update)?).map_err(StdError::from)?;
+        let action = format!("{}_hooks:{}", self.namespace, json);
+        if !self.assert_owner_threshold(deps.storage, &info.sender, &action)? {
+            return Ok(Response::new()
+                .add_attribute("action", "update_owner_hooks")
+                .add_attribute("status", "pending_approval")
+                .add_attribute("sender", info.sender));
+        }
+
+        let registry = Hooks::new(self.namespace);
+        let mut hooks = registry.list(deps.storage)?;
+        let hook = match update {
+            OwnerHooksUpdate::AddHook { addr } => {
+                let validated = deps.api.addr_validate(&addr)?;
+                if hooks.contains(&validated) {
+                    return Err(OwnerError::HookAlreadyRegistered { addr });
+                }
+                hooks.push(validated.clone());
+                validated
+            }
+            OwnerHooksUpdate::RemoveHook { addr } => {
+                let validated = deps.api.addr_validate(&addr)?;
+                if !hooks.contains(&validated) {
+                    return Err(OwnerError::HookNotRegistered { addr });
+                }
+                hooks.retain(|hook| hook != &validated);
+                validated
+            }
+        };
+        registry.save(deps.storage, hooks)?;
+
+        Ok(Response::new()
+            .add_attribute("action", "update_owner_hooks")
+            .add_attribute("label", self.label())
+            .add_attribute("hook", hook)
+            .add_attribute("sender", info.sender))
+    }
+
+    //--------------------------------------------------------------------------------------------------
+    // Assertions
+    //--------------------------------------------------------------------------------------------------
+    /// Similar to is_owner() except it raises an exception if caller is not current owner. Always
+    /// fails in multi-owner mode, see assert_owner_threshold().
+    pub fn assert_owner(&self, storage: &'a dyn Storage, caller: &Addr) -> OwnerResult<()> {
+        self.state(storage)?.assert_owner(caller)
+    }
+
+    /// Threshold-aware counterpart of assert_owner() for contract-defined privileged actions.
+    /// In multi-owner mode it records the caller's approval of `action` and returns true once
+    /// enough owners have approved it. With a single owner it returns true if caller is the owner.
+    pub fn assert_owner_threshold(
+        &self,
+        storage: &'a mut dyn Storage,
+        caller: &Addr,
+        action: &str,
+    ) -> OwnerResult<bool> {
+        let (new_state, reached) = self.state(storage)?.approve(caller, action)?;
+        if let Some(new_state) = new_state {
+            self.state.save(storage, &new_state)?;
+        }
+        Ok(reached)
+    }
+
+    pub fn assert_proposed(&self, storage: &'a dyn Storage, caller: &Addr) -> OwnerResult<()> {
+        self.state(storage)?.assert_proposed(caller)
+    }
+
+    #[cfg(feature = "emergency-owner")]
+    pub fn assert_emergency_owner(
+        &self,
+        storage: &'a dyn Storage,
+        caller: &Addr,
+    ) -> OwnerResult<()> {
+        self.state(storage)?.assert_emergency_owner(caller)
+    }
+
+    /// Same as assert_owner() but hands out a token proving the check was done
+    pub fn authorize_owner(
+        &self,
+        storage: &'a dyn Storage,
+        caller: &Addr,
+    ) -> OwnerResult<OwnerAuth<M>> {
+        self.assert_owner(storage, caller)?;
+        Ok(OwnerAuth(PhantomData))
+    }
+
+    /// Same as assert_emergency_owner() but hands out a token proving the check was done
+    #[cfg(feature = "emergency-owner")]
+    pub fn authorize_emergency_owner(
+        &self,
+        storage: &'a dyn Storage,
+        caller: &Addr,
+    ) -> OwnerResult<EmergencyOwnerAuth<M>> {
+        self.assert_emergency_owner(storage, caller)?;
+        Ok(EmergencyOwnerAuth(PhantomData))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    //--------------------------------------------------------------------------------------------------
+    // Test invalid state transitions
+    //--------------------------------------------------------------------------------------------------
+
+    #[cfg(feature = "emergency-owner")]
+    use crate::only_emergency_owner;
+    use std::any::TypeId;
+    use std::collections::BTreeSet;
+
+    use crate::owner::{
+        CwOwnableAction, CwOwnableOwnership, MultiOwnerState, OwnerPolicy, OwnerState,
+        ABOLITION_DELAY, MAX_PENDING_APPROVALS,
+    };
+    use crate::OwnerUpdate::{
+        AbolishOwnerRole, AcceptProposed, CancelAbolition, ClearProposed, ConfirmAbolition,
+        ProposeNewOwner, WithdrawApproval,
+    };
+    #[cfg(feature = "emergency-owner")]
+    use crate::OwnerUpdate::{ClearEmergencyOwner, ResignEmergencyOwner, SetEmergencyOwner};
+    use crate::{
+        only_owner, owner_execute, owner_query, HooksResponse, Owner, OwnerAuth, OwnerChangedHook,
+        OwnerError, OwnerEvent, OwnerHistoryRecord, OwnerHookMsg, OwnerHooksUpdate, OwnerInit,
+        OwnerResponse, OwnerUpdate, OwnershipProposed, OwnershipProposedMsg, PendingApproval,
+        TRANSITIONS,
+    };
+    use cosmwasm_schema::{cw_serde, QueryResponses};
+    use cosmwasm_std::testing::{
+        mock_dependencies, mock_env, mock_info, MockApi, MockQuerier, MockStorage,
+    };
+    #[cfg(feature = "emergency-owner")]
+    use cosmwasm_std::Attribute;
+    use cosmwasm_std::{
+        from_binary, from_slice, to_binary, Addr, Api, Binary, ContractInfoResponse,
+        ContractResult, CosmosMsg, Deps, DepsMut, Empty, Env, MessageInfo, OwnedDeps, Response,
+        StdError, StdResult, Storage, SystemError, SystemResult, Timestamp, WasmMsg, WasmQuery,
+    };
+    use cw_storage_plus::Item;
+    use cw_utils::Expiration;
+
+    #[test]
+    fn invalid_uninitialized_state_transitions() {
+        let mut deps = mock_dependencies();
+        let sender = Addr::unchecked("peter_parker");
+        let info = mock_info(sender.as_ref(), &[]);
+        let owner = Owner::new("xyz");
+
+        let err = owner
+            .update::<Empty, Empty>(
+                deps.as_mut(),
+                mock_env(),
+                info.clone(),
+                ProposeNewOwner {
+                    proposed: "abc".to_string(),
+                    expiry: None,
+                },
+            )
+            .unwrap_err();
+        assert!(matches!(err, OwnerError::StateTransitionError { .. }));
+
+        let err = owner
+            .update::<Empty, Empty>(deps.as_mut(), mock_env(), info.clone(), ClearProposed)
+            .unwrap_err();
+        assert!(matches!(err, OwnerError::StateTransitionError { .. }));
+
+        let err = owner
+            .update::<Empty, Empty>(deps.as_mut(), mock_env(), info.clone(), AcceptProposed)
+            .unwrap_err();
+        assert!(matches!(err, OwnerError::StateTransitionError { .. }));
+
+        let err = owner
+            .update::<Empty, Empty>(deps.as_mut(), mock_env(), info.clone(), AbolishOwnerRole)
+            .unwrap_err();
+        assert!(matches!(err, OwnerError::StateTransitionError { .. }));
+
+        #[cfg(feature = "emergency-owner")]
+        {
+            let err = owner
+                .update::<Empty, Empty>(
+                    deps.as_mut(),
+                    mock_env(),
+                    info.clone(),
+                    SetEmergencyOwner {
+                        emergency_owner: "xyz".to_string(),
+                    },
+                )
+                .unwrap_err();
+
+            assert!(matches!(err, OwnerError::StateTransitionError { .. }));
+            let err = owner
+                .update::<Empty, Empty>(
+                    deps.as_mut(),
+                    mock_env(),
+                    info.clone(),
+                    ClearEmergencyOwner,
+                )
+                .unwrap_err();
+            assert!(matches!(err, OwnerError::StateTransitionError { .. }));
+
+            let err = owner
+                .update::<Empty, Empty>(deps.as_mut(), mock_env(), info, ResignEmergencyOwner)
+                .unwrap_err();
+            assert!(matches!(err, OwnerError::StateTransitionError { .. }));
+        }
+    }
+
+    #[test]
+    fn invalid_owner_set_no_proposed_state_transitions() {
+        let mut deps = mock_dependencies();
+        let sender = Addr::unchecked("peter_parker");
+        let info = mock_info(sender.as_ref(), &[]);
+        let owner = Owner::new("xyz");
+
+        let mut_deps = deps.as_mut();
+
+        owner
+            .initialize(
+                mut_deps.storage,
+                mut_deps.api,
+                OwnerInit::SetInitialOwner {
+                    owner: sender.to_string(),
+                },
+            )
+            .unwrap();
+
+        let err = owner
+            .initialize(
+                mut_deps.storage,
+                mut_deps.api,
+                OwnerInit::SetInitialOwner {
+                    owner: "abc".to_string(),
+                },
+            )
+            .unwrap_err();
+        assert!(matches!(err, OwnerError::StateTransitionError { .. }));
+
+        let err = owner
+            .update::<Empty, Empty>(deps.as_mut(), mock_env(), info.clone(), ClearProposed)
+            .unwrap_err();
+        assert!(matches!(err, OwnerError::StateTransitionError { .. }));
+
+        let err = owner
+            .update::<Empty, Empty>(deps.as_mut(), mock_env(), info, AcceptProposed)
+            .unwrap_err();
+        assert!(matches!(err, OwnerError::StateTransitionError { .. }));
+    }
+
+    #[test]
+    fn invalid_owner_set_with_proposed_state_transitions() {
+        let mut deps = mock_dependencies();
+        let sender = Addr::unchecked("peter_parker");
+        let info = mock_info(sender.as_ref(), &[]);
+        let owner = Owner::new("xyz");
+
+        let mut_deps = deps.as_mut();
+
+        owner
+            .initialize(
+                mut_deps.storage,
+                mut_deps.api,
+                OwnerInit::SetInitialOwner {
+                    owner: sender.to_string(),
+                },
+            )
+            .unwrap();
+
+        owner
+            .update::<Empty, Empty>(
+                mut_deps,
+                mock_env(),
+                info.clone(),
+                ProposeNewOwner {
+                    proposed: "abc".to_string(),
+                    expiry: None,
+                },
+            )
+            .unwrap();
+
+        let mut_deps = deps.as_mut();
+
+        let err = owner
+            .initialize(
+                mut_deps.storage,
+                mut_deps.api,
+                OwnerInit::SetInitialOwner {
+                    owner: "abc".to_string(),
+                },
+            )
+            .unwrap_err();
+        assert!(matches!(err, OwnerError::StateTransitionError { .. }));
+
+        let err = owner
+            .update::<Empty, Empty>(
+                deps.as_mut(),
+                mock_env(),
+                info.clone(),
+                ProposeNewOwner {
+                    proposed: "efg".to_string(),
+                    expiry: None,
+                },
+            )
+            .unwrap_err();
+        assert!(matches!(err, OwnerError::StateTransitionError { .. }));
+    }
+
+    #[test]
+    fn invalid_owner_role_abolished_state_transitions() {
+        let mut deps = mock_dependencies();
+        let sender = Addr::unchecked("peter_parker");
+        let info = mock_info(sender.as_ref(), &[]);
+        let owner = Owner::new("xyz");
+
+        let mut_deps = deps.as_mut();
+
+        owner
+            .initialize(mut_deps.storage, mut_deps.api, OwnerInit::AbolishOwnerRole)
+            .unwrap();
+
+        let err = owner
+            .initialize(
+                mut_deps.storage,
+                mut_deps.api,
+                OwnerInit::SetInitialOwner {
+                    owner: "abc".to_string(),
+                },
+            )
+            .unwrap_err();
+        assert!(matches!(err, OwnerError::StateTransitionError { .. }));
+
+        let err = owner
+            .update::<Empty, Empty>(
+                deps.as_mut(),
+                mock_env(),
+                info.clone(),
+                ProposeNewOwner {
+                    proposed: "efg".to_string(),
+                    expiry: None,
+                },
+            )
+            .unwrap_err();
+        assert!(matches!(err, OwnerError::StateTransitionError { .. }));
+
+        let err = owner
+            .update::<Empty, Empty>(deps.as_mut(), mock_env(), info.clone(), ClearProposed)
+            .unwrap_err();
+        assert!(matches!(err, OwnerError::StateTransitionError { .. }));
+
+        let err = owner
+            .update::<Empty, Empty>(deps.as_mut(), mock_env(), info.clone(), AcceptProposed)
+            .unwrap_err();
+        assert!(matches!(err, OwnerError::StateTransitionError { .. }));
+
+        let err = owner
+            .update::<Empty, Empty>(deps.as_mut(), mock_env(), info.clone(), AbolishOwnerRole)
+            .unwrap_err();
+        assert!(matches!(err, OwnerError::StateTransitionError { .. }));
+
+        #[cfg(feature = "emergency-owner")]
+        {
+            let err = owner
+                .update::<Empty, Empty>(
+                    deps.as_mut(),
+                    mock_env(),
+                    info.clone(),
+                    SetEmergencyOwner {
+                        emergency_owner: "xyz".to_string(),
+                    },
+                )
+                .unwrap_err();
+            assert!(matches!(err, OwnerError::StateTransitionError { .. }));
+
+            let err = owner
+                .update::<Empty, Empty>(
+                    deps.as_mut(),
+                    mock_env(),
+                    info.clone(),
+                    ClearEmergencyOwner,
+                )
+                .unwrap_err();
+            assert!(matches!(err, OwnerError::StateTransitionError { .. }));
+
+            let err = owner
+                .update::<Empty, Empty>(deps.as_mut(), mock_env(), info, ResignEmergencyOwner)
+                .unwrap_err();
+            assert!(matches!(err, OwnerError::StateTransitionError { .. }));
+        }
+    }
+
+    #[test]
+    fn errors_describe_failed_transitions() {
+        let mut deps = mock_dependencies();
+        let owner = Owner::new("xyz");
+
+        let err = owner
+            .update::<Empty, Empty>(
+                deps.as_mut(),
+                mock_env(),
+                mock_info("peter_parker", &[]),
+                AcceptProposed,
+            )
+            .unwrap_err();
+        assert_eq!(
+            err,
+            OwnerError::StateTransitionError {
+                state: "Uninitialized".to_string(),
+                attempted: "accept_proposed".to_string(),
+            }
+        );
+        assert_eq!(
+            err.to_string(),
+            "Owner state transition was not valid: cannot accept_proposed in the Uninitialized state"
+        );
+
+        let mut_deps = deps.as_mut();
+        owner
+            .initialize(
+                mut_deps.storage,
+                mut_deps.api,
+                OwnerInit::SetInitialOwner {
+                    owner: "peter_parker".to_string(),
+                },
+            )
+            .unwrap();
+        let err = owner
+            .initialize(mut_deps.storage, mut_deps.api, OwnerInit::AbolishOwnerRole)
+            .unwrap_err();
+        assert_eq!(
+            err,
+            OwnerError::StateTransitionError {
+                state: "Base".to_string(),
+                attempted: "abolish_owner_role".to_string(),
+            }
+        );
+
+        let err = owner
+            .update::<Empty, Empty>(
+                deps.as_mut(),
+                mock_env(),
+                mock_info("doc_oc", &[]),
+                ProposeNewOwner {
+                    proposed: "doc_oc".to_string(),
+                    expiry: None,
+                },
+            )
+            .unwrap_err();
+        assert_eq!(
+            err,
+            OwnerError::NotOwner {
+                expected: "peter_parker".to_string(),
+                actual: "doc_oc".to_string(),
+            }
+        );
+
+        owner
+            .update::<Empty, Empty>(
+                deps.as_mut(),
+                mock_env(),
+                mock_info("peter_parker", &[]),
+                ProposeNewOwner {
+                    proposed: "miles_morales".to_string(),
+                    expiry: None,
+                },
+            )
+            .unwrap();
+        let err = owner
+            .update::<Empty, Empty>(
+                deps.as_mut(),
+                mock_env(),
+                mock_info("doc_oc", &[]),
+                AcceptProposed,
+            )
+            .unwrap_err();
+        assert_eq!(
+            err,
+            OwnerError::NotProposedOwner {
+                expected: "miles_morales".to_string(),
+                actual: "doc_oc".to_string(),
+            }
+        );
+        assert_eq!(
+            err.to_string(),
+            "Caller is not the proposed owner: expected miles_morales, got doc_oc"
+        );
+    }
+
+    //--------------------------------------------------------------------------------------------------
+    // Test permissions
+    //--------------------------------------------------------------------------------------------------
+
+    #[test]
+    fn initialize_owner_permissions() {
+        let mut deps = mock_dependencies();
+        let mut_deps = deps.as_mut();
+        let owner = Owner::new("xyz");
+
+        // Anyone can initialize
+        owner
+            .initialize(mut_deps.storage, mut_deps.api, OwnerInit::AbolishOwnerRole)
+            .unwrap();
+
+        let mut deps = mock_dependencies();
+        let mut_deps = deps.as_mut();
+
+        owner
+            .initialize(
+                mut_deps.storage,
+                mut_deps.api,
+                OwnerInit::SetInitialOwner {
+                    owner: "xyz".to_string(),
+                },
+            )
+            .unwrap();
+    }
+
+    #[test]
+    fn propose_new_owner_permissions() {
+        let mut deps = mock_dependencies();
+        let sender = Addr::unchecked("peter_parker");
+        let owner = Owner::new("xyz");
+
+        let mut_deps = deps.as_mut();
+        owner
+            .initialize(
+                mut_deps.storage,
+                mut_deps.api,
+                OwnerInit::SetInitialOwner {
+                    owner: sender.to_string(),
+                },
+            )
+            .unwrap();
+
+        let bad_guy = Addr::unchecked("doc_oc");
+        let info = mock_info(bad_guy.as_ref(), &[]);
+        let err = owner
+            .update::<Empty, Empty>(
+                mut_deps,
+                mock_env(),
+                info,
+                ProposeNewOwner {
+                    proposed: bad_guy.to_string(),
+                    expiry: None,
+                },
+            )
+            .unwrap_err();
+
+        assert!(matches!(err, OwnerError::NotOwner { .. }))
+    }
+
+    #[test]
+    fn clear_proposed_permissions() {
+        let mut deps = mock_dependencies();
+        let sender = Addr::unchecked("peter_parker");
+        let info = mock_info(sender.as_ref(), &[]);
+        let owner = Owner::new("xyz");
+
+        let mut_deps = deps.as_mut();
+        owner
+            .initialize(
+                mut_deps.storage,
+                mut_deps.api,
+                OwnerInit::SetInitialOwner {
+                    owner: sender.to_string(),
+                },
+            )
+            .unwrap();
+        owner
+            .update::<Empty, Empty>(
+                mut_deps,
+                mock_env(),
+                info,
+                ProposeNewOwner {
+                    proposed: "miles_morales".to_string(),
+                    expiry: None,
+                },
+            )
+            .unwrap();
+
+        let bad_guy = Addr::unchecked("doc_oc");
+        let info = mock_info(bad_guy.as_ref(), &[]);
+        let err = owner
+            .update::<Empty, Empty>(deps.as_mut(), mock_env(), info, ClearProposed)
+            .unwrap_err();
+
+        assert!(matches!(err, OwnerError::NotOwner { .. }))
+    }
+
+    #[test]
+    fn accept_proposed_permissions() {
+        let mut deps = mock_dependencies();
+        let sender = Addr::unchecked("peter_parker");
+        let info = mock_info(sender.as_ref(), &[]);
+        let owner = Owner::new("xyz");
+
+        let mut_deps = deps.as_mut();
+        owner
+            .initialize(
+                mut_deps.storage,
+                mut_deps.api,
+                OwnerInit::SetInitialOwner {
+                    owner: sender.to_string(),
+                },
+            )
+            .unwrap();
+        owner
+            .update::<Empty, Empty>(
+                mut_deps,
+                mock_env(),
+                info,
+                ProposeNewOwner {
+                    proposed: "miles_morales".to_string(),
+                    expiry: None,
+                },
+            )
+            .unwrap();
+
+        let bad_guy = Addr::unchecked("doc_oc");
+        let info = mock_info(bad_guy.as_ref(), &[]);
+        let err = owner
+            .update::<Empty, Empty>(deps.as_mut(), mock_env(), info, AcceptProposed)
+            .unwrap_err();
+
+        assert!(matches!(err, OwnerError::NotProposedOwner { .. }))
+    }
+
+    #[test]
+    fn abolish_owner_role_permissions() {
+        let mut deps = mock_dependencies();
+        let sender = Addr::unchecked("peter_parker");
+        let owner = Owner::new("xyz");
+
+        let mut_deps = deps.as_mut();
+        owner
+            .initialize(
+                mut_deps.storage,
+                mut_deps.api,
+                OwnerInit::SetInitialOwner {
+                    owner: sender.to_string(),
+                },
+            )
+            .unwrap();
+
+        let bad_guy = Addr::unchecked("doc_oc");
+        let info = mock_info(bad_guy.as_ref(), &[]);
+        let err = owner
+            .update::<Empty, Empty>(deps.as_mut(), mock_env(), info, AbolishOwnerRole)
+            .unwrap_err();
+
+        assert!(matches!(err, OwnerError::NotOwner { .. }))
+    }
+
+    #[cfg(feature = "emergency-owner")]
+    #[test]
+    fn set_emergency_owner_role_permissions() {
+        let mut deps = mock_dependencies();
+        let sender = Addr::unchecked("peter_parker");
+        let owner = Owner::new("xyz");
+
+        let mut_deps = deps.as_mut();
+        owner
+            .initialize(
+                mut_deps.storage,
+                mut_deps.api,
+                OwnerInit::SetInitialOwner {
+                    owner: sender.to_string(),
+                },
+            )
+            .unwrap();
+
+        let bad_guy = Addr::unchecked("doc_oc");
+        let info = mock_info(bad_guy.as_ref(), &[]);
+        let err = owner
+            .update::<Empty, Empty>(
+                deps.as_mut(),
+                mock_env(),
+                info,
+                SetEmergencyOwner {
+                    emergency_owner: bad_guy.to_string(),
+                },
+            )
+            .unwrap_err();
+
+        assert!(matches!(err, OwnerError::NotOwner { .. }))
+    }
+
+    #[cfg(feature = "emergency-owner")]
+    #[test]
+    fn clear_emergency_owner_role_permissions() {
+        let mut deps = mock_dependencies();
+        let sender = Addr::unchecked("peter_parker");
+        let owner = Owner::new("xyz");
+
+        let mut_deps = deps.as_mut();
+        owner
+            .initialize(
+                mut_deps.storage,
+                mut_deps.api,
+                OwnerInit::SetInitialOwner {
+                    owner: sender.to_string(),
+                },
+            )
+            .unwrap();
+
+        let bad_guy = Addr::unchecked("doc_oc");
+        let info = mock_info(bad_guy.as_ref(), &[]);
+        let err = owner
+            .update::<Empty, Empty>(deps.as_mut(), mock_env(), info, ClearEmergencyOwner)
+            .unwrap_err();
+
+        assert!(matches!(err, OwnerError::NotOwner { .. }))
+    }
+    #[cfg(feature = "emergency-owner")]
+    #[test]
+    fn emergency_owner_with_proposed_permissions() {
+        let mut deps = mock_dependencies();
+        let sender = Addr::unchecked("peter_parker");
+        let info = mock_info(sender.as_ref(), &[]);
+        let owner = Owner::new("xyz");
+
+        let mut_deps = deps.as_mut();
+        owner
+            .initialize(
+                mut_deps.storage,
+                mut_deps.api,
+                OwnerInit::SetInitialOwner {
+                    owner: sender.to_string(),
+                },
+            )
+            .unwrap();
+        owner
+            .update::<Empty, Empty>(
+                mut_deps,
+                mock_env(),
+                info,
+                ProposeNewOwner {
+                    proposed: "miles_morales".to_string(),
+                    expiry: None,
+                },
+            )
+            .unwrap();
+
+        let bad_guy = Addr::unchecked("doc_oc");
+        let info = mock_info(bad_guy.as_ref(), &[]);
+        let err = owner
+            .update::<Empty, Empty>(
+                deps.as_mut(),
+                mock_env(),
+                info.clone(),
+                SetEmergencyOwner {
+                    emergency_owner: bad_guy.to_string(),
+                },
+            )
+            .unwrap_err();
+        assert!(matches!(err, OwnerError::NotOwner { .. }));
+
+        let err = owner
+            .update::<Empty, Empty>(deps.as_mut(), mock_env(), info, ClearEmergencyOwner)
+            .unwrap_err();
+        assert!(matches!(err, OwnerError::NotOwner { .. }));
+
+        // The proposed owner has no say over the emergency owner either
+        let info = mock_info("miles_morales", &[]);
+        let err = owner
+            .update::<Empty, Empty>(deps.as_mut(), mock_env(), info, ClearEmergencyOwner)
+            .unwrap_err();
+        assert!(matches!(err, OwnerError::NotOwner { .. }));
+    }
+    #[cfg(feature = "emergency-owner")]
+    #[test]
+    fn resign_emergency_owner_role_permissions() {
+        let mut deps = mock_dependencies();
+        let sender = Addr::unchecked("peter_parker");
+        let info = mock_info(sender.as_ref(), &[]);
+        let owner = Owner::new("xyz");
+
+        let mut_deps = deps.as_mut();
+        owner
+            .initialize(
+                mut_deps.storage,
+                mut_deps.api,
+                OwnerInit::SetInitialOwner {
+                    owner: sender.to_string(),
+                },
+            )
+            .unwrap();
+        owner
+            .update::<Empty, Empty>(
+                mut_deps,
+                mock_env(),
+                info.clone(),
+                SetEmergencyOwner {
+                    emergency_owner: "miles_morales".to_string(),
+                },
+            )
+            .unwrap();
+
+        // Not even the owner can resign on behalf of the emergency owner
+        let err = owner
+            .update::<Empty, Empty>(deps.as_mut(), mock_env(), info, ResignEmergencyOwner)
+            .unwrap_err();
+        assert_eq!(err, OwnerError::NotEmergencyOwner {});
+
+        let bad_guy = Addr::unchecked("doc_oc");
+        let info = mock_info(bad_guy.as_ref(), &[]);
+        let err = owner
+            .update::<Empty, Empty>(deps.as_mut(), mock_env(), info, ResignEmergencyOwner)
+            .unwrap_err();
+        assert_eq!(err, OwnerError::NotEmergencyOwner {});
+    }
+
+    //--------------------------------------------------------------------------------------------------
+    // Test proposal expiry
+    //--------------------------------------------------------------------------------------------------
+
+    #[test]
+    fn propose_new_owner_with_past_expiry() {
+        let mut deps = mock_dependencies();
+        let sender = Addr::unchecked("peter_parker");
+        let info = mock_info(sender.as_ref(), &[]);
+        let owner = Owner::new("xyz");
+        let env = mock_env();
+
+        let mut_deps = deps.as_mut();
+        owner
+            .initialize(
+                mut_deps.storage,
+                mut_deps.api,
+                OwnerInit::SetInitialOwner {
+                    owner: sender.to_string(),
+                },
+            )
+            .unwrap();
+
+        let err = owner
+            .update::<Empty, Empty>(
+                mut_deps,
+                env.clone(),
+                info,
+                ProposeNewOwner {
+                    proposed: "miles_morales".to_string(),
+                    expiry: Some(Expiration::AtHeight(env.block.height)),
+                },
+            )
+            .unwrap_err();
+
+        assert_eq!(err, OwnerError::InvalidExpiry {})
+    }
+
+    #[test]
+    fn accept_expired_proposal() {
+        let mut deps = mock_dependencies();
+        let original_owner = Addr::unchecked("peter_parker");
+        let proposed_owner = Addr::unchecked("miles_morales");
+        let info = mock_info(original_owner.as_ref(), &[]);
+        let owner = Owner::new("xyz");
+        let mut env = mock_env();
+        let expiry = Expiration::AtTime(env.block.time.plus_seconds(100));
+
+        let mut_deps = deps.as_mut();
+        owner
+            .initialize(
+                mut_deps.storage,
+                mut_deps.api,
+                OwnerInit::SetInitialOwner {
+                    owner: original_owner.to_string(),
+                },
+            )
+            .unwrap();
+
+        owner
+            .update::<Empty, Empty>(
+                mut_deps,
+                env.clone(),
+                info,
+                ProposeNewOwner {
+                    proposed: proposed_owner.to_string(),
+                    expiry: Some(expiry),
+                },
+            )
+            .unwrap();
+
+        let res = owner.query(deps.as_ref().storage).unwrap();
+        assert_eq!(res.proposed_expiry, Some(expiry));
+
+        env.block.time = env.block.time.plus_seconds(100);
+        let info = mock_info(proposed_owner.as_ref(), &[]);
+        let err = owner
+            .update::<Empty, Empty>(deps.as_mut(), env, info, AcceptProposed)
+            .unwrap_err();
+
+        assert_eq!(err, OwnerError::ProposalExpired {});
+
+        let current = owner.current(deps.as_ref().storage).unwrap();
+        assert_eq!(current, Some(original_owner));
+    }
+
+    #[test]
+    fn accept_proposal_before_expiry() {
+        let mut deps = mock_dependencies();
+        let original_owner = Addr::unchecked("peter_parker");
+        let proposed_owner = Addr::unchecked("miles_morales");
+        let info = mock_info(original_owner.as_ref(), &[]);
+        let owner = Owner::new("xyz");
+        let mut env = mock_env();
+
+        let mut_deps = deps.as_mut();
+        owner
+            .initialize(
+                mut_deps.storage,
+                mut_deps.api,
+                OwnerInit::SetInitialOwner {
+                    owner: original_owner.to_string(),
+                },
+            )
+            .unwrap();
+
+        owner
+            .update::<Empty, Empty>(
+                mut_deps,
+                env.clone(),
+                info,
+                ProposeNewOwner {
+                    proposed: proposed_owner.to_string(),
+                    expiry: Some(Expiration::AtHeight(env.block.height + 10)),
+                },
+            )
+            .unwrap();
+
+        env.block.height += 9;
+        let info = mock_info(proposed_owner.as_ref(), &[]);
+        owner
+            .update::<Empty, Empty>(deps.as_mut(), env, info, AcceptProposed)
+            .unwrap();
+
+        let res = owner.query(deps.as_ref().storage).unwrap();
+        assert_eq!(res.owner, Some(proposed_owner.to_string()));
+        assert_eq!(res.proposed_expiry, None);
+    }
+
+    //--------------------------------------------------------------------------------------------------
+    // Test acceptance timelock
+    //--------------------------------------------------------------------------------------------------
+
+    #[test]
+    fn accept_proposed_before_timelock() {
+        let mut deps = mock_dependencies();
+        let original_owner = Addr::unchecked("peter_parker");
+        let proposed_owner = Addr::unchecked("miles_morales");
+        let info = mock_info(original_owner.as_ref(), &[]);
+        let owner = Owner::new("xyz");
+        let mut env = mock_env();
+        let accept_after = env.block.time.plus_seconds(3600);
+
+        let mut_deps = deps.as_mut();
+        owner
+            .initialize(
+                mut_deps.storage,
+                mut_deps.api,
+                OwnerInit::SetInitialOwnerWithTimelock {
+                    owner: original_owner.to_string(),
+                    accept_delay: 3600,
+                },
+            )
+            .unwrap();
+
+        owner
+            .update::<Empty, Empty>(
+                mut_deps,
+                env.clone(),
+                info,
+                ProposeNewOwner {
+                    proposed: proposed_owner.to_string(),
+                    expiry: None,
+                },
+            )
+            .unwrap();
+
+        let res = owner.query(deps.as_ref().storage).unwrap();
+        assert_eq!(res.accept_after, Some(accept_after));
+
+        env.block.time = env.block.time.plus_seconds(3599);
+        let info = mock_info(proposed_owner.as_ref(), &[]);
+        let err = owner
+            .update::<Empty, Empty>(deps.as_mut(), env.clone(), info.clone(), AcceptProposed)
+            .unwrap_err();
+
+        assert_eq!(err, OwnerError::TimelockNotElapsed { accept_after });
+
+        env.block.time = env.block.time.plus_seconds(1);
+        owner
+            .update::<Empty, Empty>(deps.as_mut(), env, info, AcceptProposed)
+            .unwrap();
+
+        let res = owner.query(deps.as_ref().storage).unwrap();
+        assert_eq!(res.owner, Some(proposed_owner.to_string()));
+        assert_eq!(res.accept_after, None);
+    }
+
+    #[test]
+    fn propose_with_expiry_inside_timelock() {
+        let mut deps = mock_dependencies();
+        let sender = Addr::unchecked("peter_parker");
+        let info = mock_info(sender.as_ref(), &[]);
+        let owner = Owner::new("xyz");
+        let env = mock_env();
+
+        let mut_deps = deps.as_mut();
+        owner
+            .initialize(
+                mut_deps.storage,
+                mut_deps.api,
+                OwnerInit::SetInitialOwnerWithTimelock {
+                    owner: sender.to_string(),
+                    accept_delay: 3600,
+                },
+            )
+            .unwrap();
+
+        let err = owner
+            .update::<Empty, Empty>(
+                mut_deps,
+                env.clone(),
+                info,
+                ProposeNewOwner {
+                    proposed: "miles_morales".to_string(),
+                    expiry: Some(Expiration::AtTime(env.block.time.plus_seconds(3600))),
+                },
+            )
+            .unwrap_err();
+
+        assert_eq!(err, OwnerError::InvalidExpiry {});
+
+        let err = owner
+            .update::<Empty, Empty>(
+                deps.as_mut(),
+                env.clone(),
+                mock_info(sender.as_ref(), &[]),
+                ProposeNewOwner {
+                    proposed: "miles_morales".to_string(),
+                    expiry: Some(Expiration::AtHeight(env.block.height + 1000)),
+                },
+            )
+            .unwrap_err();
+
+        assert_eq!(err, OwnerError::InvalidExpiryHeight {})
+    }
+
+    //--------------------------------------------------------------------------------------------------
+    // Test delayed abolition
+    //--------------------------------------------------------------------------------------------------
+
+    fn init_abolition_proposed(deps: DepsMut, owner: &Owner) {
+        let info = mock_info("peter_parker", &[]);
+        owner
+            .initialize(
+                deps.storage,
+                deps.api,
+                OwnerInit::SetInitialOwner {
+                    owner: "peter_parker".to_string(),
+                },
+            )
+            .unwrap();
+        owner
+            .update::<Empty, Empty>(deps, mock_env(), info, AbolishOwnerRole)
+            .unwrap();
+    }
+
+    #[test]
+    fn invalid_abolition_proposed_state_transitions() {
+        let mut deps = mock_dependencies();
+        let info = mock_info("peter_parker", &[]);
+        let owner = Owner::new("xyz");
+        init_abolition_proposed(deps.as_mut(), &owner);
+
+        let err = owner
+            .update::<Empty, Empty>(
+                deps.as_mut(),
+                mock_env(),
+                info.clone(),
+                ProposeNewOwner {
+                    proposed: "miles_morales".to_string(),
+                    expiry: None,
+                },
+            )
+            .unwrap_err();
+        assert!(matches!(err, OwnerError::StateTransitionError { .. }));
+
+        let err = owner
+            .update::<Empty, Empty>(deps.as_mut(), mock_env(), info.clone(), AbolishOwnerRole)
+            .unwrap_err();
+        assert!(matches!(err, OwnerError::StateTransitionError { .. }));
+
+        let err = owner
+            .update::<Empty, Empty>(deps.as_mut(), mock_env(), info, ClearProposed)
+            .unwrap_err();
+        assert!(matches!(err, OwnerError::StateTransitionError { .. }));
+
+        // Nothing to cancel or confirm without a pending abolition
+        let mut deps = mock_dependencies();
+        let mut_deps = deps.as_mut();
+        owner
+            .initialize(
+                mut_deps.storage,
+                mut_deps.api,
+                OwnerInit::SetInitialOwner {
+                    owner: "peter_parker".to_string(),
+                },
+            )
+            .unwrap();
+        let info = mock_info("peter_parker", &[]);
+        let err = owner
+            .update::<Empty, Empty>(deps.as_mut(), mock_env(), info.clone(), CancelAbolition)
+            .unwrap_err();
+        assert!(matches!(err, OwnerError::StateTransitionError { .. }));
+
+        let err = owner
+            .update::<Empty, Empty>(deps.as_mut(), mock_env(), info, ConfirmAbolition)
+            .unwrap_err();
+        assert!(matches!(err, OwnerError::StateTransitionError { .. }));
+    }
+
+    #[test]
+    fn abolition_permissions() {
+        let mut deps = mock_dependencies();
+        let owner = Owner::new("xyz");
+        init_abolition_proposed(deps.as_mut(), &owner);
+
+        let mut env = mock_env();
+        env.block.time = env.block.time.plus_seconds(ABOLITION_DELAY);
+        let info = mock_info("doc_oc", &[]);
+        let err = owner
+            .update::<Empty, Empty>(deps.as_mut(), env.clone(), info.clone(), CancelAbolition)
+            .unwrap_err();
+        assert!(matches!(err, OwnerError::NotOwner { .. }));
+
+        let err = owner
+            .update::<Empty, Empty>(deps.as_mut(), env, info, ConfirmAbolition)
+            .unwrap_err();
+        assert!(matches!(err, OwnerError::NotOwner { .. }));
+    }
+
+    #[test]
+    fn confirm_abolition_before_delay() {
+        let mut deps = mock_dependencies();
+        let owner = Owner::new("xyz");
+        init_abolition_proposed(deps.as_mut(), &owner);
+
+        let mut env = mock_env();
+        let abolish_after = env.block.time.plus_seconds(ABOLITION_DELAY);
+        env.block.time = env.block.time.plus_seconds(ABOLITION_DELAY - 1);
+        let info = mock_info("peter_parker", &[]);
+        let err = owner
+            .update::<Empty, Empty>(deps.as_mut(), env, info, ConfirmAbolition)
+            .unwrap_err();
+        assert_eq!(
+            err,
+            OwnerError::AbolitionTimelockNotElapsed { abolish_after }
+        );
+    }
+
+    #[test]
+    fn cancel_abolition() {
+        let mut deps = mock_dependencies();
+        let owner = Owner::new("xyz");
+        init_abolition_proposed(deps.as_mut(), &owner);
+
+        let info = mock_info("peter_parker", &[]);
+        owner
+            .update::<Empty, Empty>(deps.as_mut(), mock_env(), info, CancelAbolition)
+            .unwrap();
+
+        let storage = deps.as_ref().storage;
+        let state = owner.state(storage).unwrap();
+        match state {
+            OwnerState::Base { .. } => {}
+            _ => panic!("Should be in the Base state"),
+        }
+
+        let res = owner.query(storage).unwrap();
+        assert_eq!(res.owner, Some("peter_parker".to_string()));
+        assert_eq!(res.abolish_after, None);
+        assert!(!res.abolished);
+    }
+
+    #[test]
+    fn abolition_delay_follows_longer_timelock() {
+        let mut deps = mock_dependencies();
+        let owner = Owner::new("xyz");
+        let env = mock_env();
+
+        let mut_deps = deps.as_mut();
+        owner
+            .initialize(
+                mut_deps.storage,
+                mut_deps.api,
+                OwnerInit::SetInitialOwnerWithTimelock {
+                    owner: "peter_parker".to_string(),
+                    accept_delay: ABOLITION_DELAY * 2,
+                },
+            )
+            .unwrap();
+        owner
+            .update::<Empty, Empty>(
+                mut_deps,
+                env.clone(),
+                mock_info("peter_parker", &[]),
+                AbolishOwnerRole,
+            )
+            .unwrap();
+
+        let abolish_after = owner.abolish_after(deps.as_ref().storage).unwrap();
+        assert_eq!(
+            abolish_after,
+            Some(env.block.time.plus_seconds(ABOLITION_DELAY * 2))
+        );
+    }
+
+    //--------------------------------------------------------------------------------------------------
+    // Test multi-owner mode
+    //--------------------------------------------------------------------------------------------------
+
+    fn init_multi_owner(storage: &mut dyn Storage, api: &dyn Api, owner: &Owner) {
+        owner
+            .initialize(
+                storage,
+                api,
+                OwnerInit::SetInitialOwners {
+                    owners: vec![
+                        "peter_parker".to_string(),
+                        "miles_morales".to_string(),
+                        "gwen_stacy".to_string(),
+                    ],
+                    threshold: 2,
+                },
+            )
+            .unwrap();
+    }
+
+    #[test]
+    fn initialize_multi_owner_validation() {
+        let mut deps = mock_dependencies();
+        let mut_deps = deps.as_mut();
+        let owner = Owner::new("xyz");
+
+        let err = owner
+            .initialize(
+                mut_deps.storage,
+                mut_deps.api,
+                OwnerInit::SetInitialOwners {
+                    owners: vec!["peter_parker".to_string()],
+                    threshold: 2,
+                },
+            )
+            .unwrap_err();
+        assert_eq!(err, OwnerError::InvalidThreshold {});
+
+        let err = owner
+            .initialize(
+                mut_deps.storage,
+                mut_deps.api,
+                OwnerInit::SetInitialOwners {
+                    owners: vec!["peter_parker".to_string()],
+                    threshold: 0,
+                },
+            )
+            .unwrap_err();
+        assert_eq!(err, OwnerError::InvalidThreshold {});
+
+        let err = owner
+            .initialize(
+                mut_deps.storage,
+                mut_deps.api,
+                OwnerInit::SetInitialOwners {
+                    owners: vec!["peter_parker".to_string(), "peter_parker".to_string()],
+                    threshold: 1,
+                },
+            )
+            .unwrap_err();
+        assert_eq!(err, OwnerError::DuplicateOwner {});
+    }
+
+    #[test]
+    fn multi_owner_approval_permissions() {
+        let mut deps = mock_dependencies();
+        let owner = Owner::new("xyz");
+        let mut_deps = deps.as_mut();
+        init_multi_owner(mut_deps.storage, mut_deps.api, &owner);
+
+        let info = mock_info("doc_oc", &[]);
+        let err = owner
+            .update::<Empty, Empty>(deps.as_mut(), mock_env(), info, AbolishOwnerRole)
+            .unwrap_err();
+        assert!(matches!(err, OwnerError::NotOwner { .. }));
+
+        let info = mock_info("peter_parker", &[]);
+        owner
+            .update::<Empty, Empty>(deps.as_mut(), mock_env(), info.clone(), AbolishOwnerRole)
+            .unwrap();
+        let err = owner
+            .update::<Empty, Empty>(deps.as_mut(), mock_env(), info, AbolishOwnerRole)
+            .unwrap_err();
+        assert_eq!(err, OwnerError::AlreadyApproved {});
+
+        // A single owner does not pass the plain owner check
+        let storage = deps.as_ref().storage;
+        let err = owner
+            .assert_owner(storage, &Addr::unchecked("peter_parker"))
+            .unwrap_err();
+        assert!(matches!(err, OwnerError::NotOwner { .. }));
+    }
+
+    #[test]
+    fn multi_owner_propose_and_accept() {
+        let mut deps = mock_dependencies();
+        let owner = Owner::new("xyz");
+        let proposed_owner = Addr::unchecked("doc_oc");
+        let mut_deps = deps.as_mut();
+        init_multi_owner(mut_deps.storage, mut_deps.api, &owner);
+
+        let propose = ProposeNewOwner {
+            proposed: proposed_owner.to_string(),
+            expiry: None,
+        };
+        let res = owner
+            .update::<Empty, Empty>(
+                deps.as_mut(),
+                mock_env(),
+                mock_info("peter_parker", &[]),
+                propose.clone(),
+            )
+            .unwrap();
+        assert_eq!(res.attributes[2].key, "status");
+        assert_eq!(res.attributes[2].value, "pending_approval");
+        assert!(res.events.is_empty());
+
+        let res = owner.query(deps.as_ref().storage).unwrap();
+        assert_eq!(res.owner, None);
+        assert_eq!(res.proposed, None);
+        let multi = res.multi_owner.unwrap();
+        assert_eq!(multi.threshold, 2);
+        assert_eq!(multi.pending.len(), 1);
+        assert_eq!(
+            multi.pending[0].approvals,
+            vec![Addr::unchecked("peter_parker")]
+        );
+
+        let res = owner
+            .update::<Empty, Empty>(
+                deps.as_mut(),
+                mock_env(),
+                mock_info("gwen_stacy", &[]),
+                propose,
+            )
+            .unwrap();
+        assert!(res.attributes.iter().all(|attr| attr.key != "status"));
+        assert_eq!(res.events.len(), 1);
+
+        let res = owner.query(deps.as_ref().storage).unwrap();
+        assert_eq!(res.proposed, Some(proposed_owner.to_string()));
+        assert!(res.multi_owner.unwrap().pending.is_empty());
+
+        owner
+            .update::<Empty, Empty>(
+                deps.as_mut(),
+                mock_env(),
+                mock_info(proposed_owner.as_ref(), &[]),
+                AcceptProposed,
+            )
+            .unwrap();
+
+        let storage = deps.as_ref().storage;
+        let state = owner.state(storage).unwrap();
+        match state {
+            OwnerState::Base { .. } => {}
+            _ => panic!("Should be in the Base state"),
+        }
+        assert_eq!(owner.owners(storage).unwrap(), vec![proposed_owner]);
+        assert_eq!(owner.multi_owner(storage).unwrap(), None);
+    }
+
+    #[test]
+    fn multi_owner_clear_proposed() {
+        let mut deps = mock_dependencies();
+        let owner = Owner::new("xyz");
+        let mut_deps = deps.as_mut();
+        init_multi_owner(mut_deps.storage, mut_deps.api, &owner);
+
+        for sender in ["peter_parker", "miles_morales"] {
+            owner
+                .update::<Empty, Empty>(
+                    deps.as_mut(),
+                    mock_env(),
+                    mock_info(sender, &[]),
+                    ProposeNewOwner {
+                        proposed: "doc_oc".to_string(),
+                        expiry: None,
+                    },
+                )
+                .unwrap();
+        }
+
+        owner
+            .update::<Empty, Empty>(
+                deps.as_mut(),
+                mock_env(),
+                mock_info("gwen_stacy", &[]),
+                ClearProposed,
+            )
+            .unwrap();
+
+        let res = owner.query(deps.as_ref().storage).unwrap();
+        assert_eq!(res.proposed, None);
+        assert_eq!(res.multi_owner.unwrap().owners.len(), 3);
+    }
+
+    #[test]
+    fn multi_owner_abolish_owner_role() {
+        let mut deps = mock_dependencies();
+        let owner = Owner::new("xyz");
+        let mut_deps = deps.as_mut();
+        init_multi_owner(mut_deps.storage, mut_deps.api, &owner);
+
+        for sender in ["miles_morales", "gwen_stacy"] {
+            owner
+                .update::<Empty, Empty>(
+                    deps.as_mut(),
+                    mock_env(),
+                    mock_info(sender, &[]),
+                    AbolishOwnerRole,
+                )
+                .unwrap();
+        }
+
+        let res = owner.query(deps.as_ref().storage).unwrap();
+        assert!(!res.abolished);
+        let abolish_after = res.abolish_after.unwrap();
+
+        let mut env = mock_env();
+        env.block.time = abolish_after;
+        owner
+            .update::<Empty, Empty>(
+                deps.as_mut(),
+                env,
+                mock_info("peter_parker", &[]),
+                ConfirmAbolition,
+            )
+            .unwrap();
+
+        let res = owner.query(deps.as_ref().storage).unwrap();
+        assert!(res.abolished);
+        assert_eq!(res.multi_owner, None);
+    }
+
+    #[cfg(feature = "emergency-owner")]
+    #[test]
+    fn multi_owner_set_emergency_owner() {
+        let mut deps = mock_dependencies();
+        let owner = Owner::new("xyz");
+        let mut_deps = deps.as_mut();
+        init_multi_owner(mut_deps.storage, mut_deps.api, &owner);
+
+        let set = SetEmergencyOwner {
+            emergency_owner: "mary_jane".to_string(),
+        };
+        owner
+            .update::<Empty, Empty>(
+                deps.as_mut(),
+                mock_env(),
+                mock_info("peter_parker", &[]),
+                set.clone(),
+            )
+            .unwrap();
+        assert_eq!(owner.emergency_owner(deps.as_ref().storage).unwrap(), None);
+
+        owner
+            .update::<Empty, Empty>(
+                deps.as_mut(),
+                mock_env(),
+                mock_info("miles_morales", &[]),
+                set,
+            )
+            .unwrap();
+        assert_eq!(
+            owner.emergency_owner(deps.as_ref().storage).unwrap(),
+            Some(Addr::unchecked("mary_jane"))
+        );
+    }
+
+    #[test]
+    fn assert_owner_threshold() {
+        let mut deps = mock_dependencies();
+        let owner = Owner::new("xyz");
+        let mut_deps = deps.as_mut();
+        init_multi_owner(mut_deps.storage, mut_deps.api, &owner);
+
+        let storage = deps.as_mut().storage;
+        let reached = owner
+            .assert_owner_threshold(storage, &Addr::unchecked("peter_parker"), "withdraw")
+            .unwrap();
+        assert!(!reached);
+
+        let err = owner
+            .assert_owner_threshold(storage, &Addr::unchecked("doc_oc"), "withdraw")
+            .unwrap_err();
+        assert!(matches!(err, OwnerError::NotOwner { .. }));
+
+        let reached = owner
+            .assert_owner_threshold(storage, &Addr::unchecked("gwen_stacy"), "withdraw")
+            .unwrap();
+        assert!(reached);
+
+        // Single owner passes straight away
+        let mut deps = mock_dependencies();
+        let mut_deps = deps.as_mut();
+        owner
+            .initialize(
+                mut_deps.storage,
+                mut_deps.api,
+                OwnerInit::SetInitialOwner {
+                    owner: "peter_parker".to_string(),
+                },
+            )
+            .unwrap();
+        let reached = owner
+            .assert_owner_threshold(
+                mut_deps.storage,
+                &Addr::unchecked("peter_parker"),
+                "withdraw",
+            )
+            .unwrap();
+        assert!(reached);
+    }
+
+    #[test]
+    fn multi_owner_withdraw_approval() {
+        let mut deps = mock_dependencies();
+        let owner = Owner::new("xyz");
+        let mut_deps = deps.as_mut();
+        init_multi_owner(mut_deps.storage, mut_deps.api, &owner);
+
+        owner
+            .update::<Empty, Empty>(
+                deps.as_mut(),
+                mock_env(),
+                mock_info("peter_parker", &[]),
+                AbolishOwnerRole,
+            )
+            .unwrap();
+        let multi = owner.multi_owner(deps.as_ref().storage).unwrap().unwrap();
+        let action = multi.pending[0].action.clone();
+        assert_eq!(action, "\"abolish_owner_role\"");
+
+        let withdraw = WithdrawApproval {
+            action: action.clone(),
+        };
+        let err = owner
+            .update::<Empty, Empty>(
+                deps.as_mut(),
+                mock_env(),
+                mock_info("doc_oc", &[]),
+                withdraw.clone(),
+            )
+            .unwrap_err();
+        assert!(matches!(err, OwnerError::NotOwner { .. }));
+
+        let err = owner
+            .update::<Empty, Empty>(
+                deps.as_mut(),
+                mock_env(),
+                mock_info("gwen_stacy", &[]),
+                withdraw.clone(),
+            )
+            .unwrap_err();
+        assert_eq!(err, OwnerError::ApprovalNotFound {});
+
+        owner
+            .update::<Empty, Empty>(
+                deps.as_mut(),
+                mock_env(),
+                mock_info("peter_parker", &[]),
+                withdraw,
+            )
+            .unwrap();
+        let multi = owner.multi_owner(deps.as_ref().storage).unwrap().unwrap();
+        assert!(multi.pending.is_empty());
+
+        // The withdrawn approval no longer counts towards the threshold
+        owner
+            .update::<Empty, Empty>(
+                deps.as_mut(),
+                mock_env(),
+                mock_info("miles_morales", &[]),
+                AbolishOwnerRole,
+            )
+            .unwrap();
+        assert_eq!(owner.abolish_after(deps.as_ref().storage).unwrap(), None);
+    }
+
+    #[test]
+    fn multi_owner_pending_approvals_are_capped() {
+        let mut deps = mock_dependencies();
+        let owner = Owner::new("xyz");
+        let mut_deps = deps.as_mut();
+        init_multi_owner(mut_deps.storage, mut_deps.api, &owner);
+
+        let peter = Addr::unchecked("peter_parker");
+        let storage = deps.as_mut().storage;
+        for i in 0..MAX_PENDING_APPROVALS {
+            let action = format!("withdraw_{}", i);
+            owner
+                .assert_owner_threshold(storage, &peter, &action)
+                .unwrap();
+        }
+
+        let err = owner
+            .assert_owner_threshold(storage, &peter, "withdraw_0")
+            .unwrap_err();
+        assert_eq!(err, OwnerError::AlreadyApproved {});
+        let err = owner
+            .assert_owner_threshold(storage, &peter, "one_too_many")
+            .unwrap_err();
+        assert_eq!(
+            err,
+            OwnerError::TooManyPendingApprovals {
+                max: MAX_PENDING_APPROVALS
+            }
+        );
+
+        // Other owners are not affected, and withdrawing makes room again
+        owner
+            .assert_owner_threshold(storage, &Addr::unchecked("gwen_stacy"), "one_too_many")
+            .unwrap();
+        owner
+            .update::<Empty, Empty>(
+                deps.as_mut(),
+                mock_env(),
+                mock_info("peter_parker", &[]),
+                WithdrawApproval {
+                    action: "withdraw_0".to_string(),
+                },
+            )
+            .unwrap();
+        owner
+            .update::<Empty, Empty>(
+                deps.as_mut(),
+                mock_env(),
+                mock_info("peter_parker", &[]),
+                AbolishOwnerRole,
+            )
+            .unwrap();
+    }
+
+    #[test]
+    fn multi_owner_drops_stale_approvals() {
+        let mut deps = mock_dependencies();
+        let owner = Owner::new("xyz");
+        let mut_deps = deps.as_mut();
+        init_multi_owner(mut_deps.storage, mut_deps.api, &owner);
+
+        owner
+            .assert_owner_threshold(
+                deps.as_mut().storage,
+                &Addr::unchecked("peter_parker"),
+                "withdraw",
+            )
+            .unwrap();
+        for (sender, proposed) in [
+            ("peter_parker", "doc_oc"),
+            ("miles_morales", "mary_jane"),
+            ("gwen_stacy", "mary_jane"),
+        ] {
+            owner
+                .update::<Empty, Empty>(
+                    deps.as_mut(),
+                    mock_env(),
+                    mock_info(sender, &[]),
+                    ProposeNewOwner {
+                        proposed: proposed.to_string(),
+                        expiry: None,
+                    },
+                )
+                .unwrap();
+        }
+
+        // The approval of proposing doc_oc was given before mary_jane got proposed
+        let multi = owner.multi_owner(deps.as_ref().storage).unwrap().unwrap();
+        assert_eq!(
+            multi.pending,
+            vec![PendingApproval {
+                action: "withdraw".to_string(),
+                approvals: vec![Addr::unchecked("peter_parker")],
+            }]
+        );
+    }
+
+    //--------------------------------------------------------------------------------------------------
+    // Test migration from legacy storage
+    //--------------------------------------------------------------------------------------------------
+
+    #[test]
+    fn migrate_from_addr_item_same_key() {
+        let mut deps = mock_dependencies();
+        let original_owner = Addr::unchecked("peter_parker");
+        let owner = Owner::new("owner");
+
+        let legacy: Item<Addr> = Item::new("owner");
+        legacy.save(deps.as_mut().storage, &original_owner).unwrap();
+
+        let mut_deps = deps.as_mut();
+        owner
+            .migrate_from_addr_item(mut_deps.storage, mut_deps.api, &mock_env(), "owner", true)
+            .unwrap();
+
+        let storage = deps.as_ref().storage;
+        let state = owner.state(storage).unwrap();
+        match state {
+            OwnerState::Base { .. } => {}
+            _ => panic!("Should be in the Base state"),
+        }
+        assert!(owner.is_owner(storage, &original_owner).unwrap());
+
+        // Running the migration again finds the Owner state rather than the legacy owner
+        let mut_deps = deps.as_mut();
+        let err = owner
+            .migrate_from_addr_item(mut_deps.storage, mut_deps.api, &mock_env(), "owner", true)
+            .unwrap_err();
+        assert_eq!(
+            err,
+            OwnerError::StateTransitionError {
+                state: "Base".to_string(),
+                attempted: "migrate".to_string(),
+            }
+        );
+    }
+
+    #[test]
+    fn migrate_from_addr_item_other_key() {
+        let mut deps = mock_dependencies();
+        let original_owner = Addr::unchecked("peter_parker");
+        let owner = Owner::new("xyz");
+
+        let legacy: Item<Addr> = Item::new("admin");
+        legacy.save(deps.as_mut().storage, &original_owner).unwrap();
+
+        let mut_deps = deps.as_mut();
+        owner
+            .migrate_from_addr_item(mut_deps.storage, mut_deps.api, &mock_env(), "admin", false)
+            .unwrap();
+        assert!(owner
+            .is_owner(deps.as_ref().storage, &original_owner)
+            .unwrap());
+        assert!(legacy.may_load(deps.as_ref().storage).unwrap().is_some());
+
+        let mut deps = mock_dependencies();
+        legacy.save(deps.as_mut().storage, &original_owner).unwrap();
+
+        let mut_deps = deps.as_mut();
+        owner
+            .migrate_from_addr_item(mut_deps.storage, mut_deps.api, &mock_env(), "admin", true)
+            .unwrap();
+        assert!(owner
+            .is_owner(deps.as_ref().storage, &original_owner)
+            .unwrap());
+        assert!(legacy.may_load(deps.as_ref().storage).unwrap().is_none());
+    }
+
+    #[test]
+    fn invalid_migrate_from_addr_item() {
+        let mut deps = mock_dependencies();
+        let owner = Owner::new("xyz");
+
+        let mut_deps = deps.as_mut();
+        let err = owner
+            .migrate_from_addr_item(mut_deps.storage, mut_deps.api, &mock_env(), "admin", true)
+            .unwrap_err();
+        assert_eq!(
+            err,
+            OwnerError::LegacyOwnerNotFound {
+                key: "admin".to_string()
+            }
+        );
+
+        let legacy: Item<Addr> = Item::new("admin");
+        legacy
+            .save(mut_deps.storage, &Addr::unchecked("peter_parker"))
+            .unwrap();
+        owner
+            .initialize(
+                mut_deps.storage,
+                mut_deps.api,
+                OwnerInit::SetInitialOwner {
+                    owner: "miles_morales".to_string(),
+                },
+            )
+            .unwrap();
+
+        let err = owner
+            .migrate_from_addr_item(mut_deps.storage, mut_deps.api, &mock_env(), "admin", true)
+            .unwrap_err();
+        assert!(matches!(err, OwnerError::StateTransitionError { .. }));
+        assert!(legacy.may_load(mut_deps.storage).unwrap().is_some());
+    }
+
+    fn migrate_cw_ownable(
+        ownership: CwOwnableOwnership,
+    ) -> (Owner<'static>, OwnedDeps<MockStorage, MockApi, MockQuerier>) {
+        let mut deps = mock_dependencies();
+        let owner = Owner::new("xyz");
+
+        let legacy: Item<CwOwnableOwnership> = Item::new("ownership");
+        legacy.save(deps.as_mut().storage, &ownership).unwrap();
+
+        let mut_deps = deps.as_mut();
+        owner
+            .migrate_from_cw_ownable(mut_deps.storage, mut_deps.api, &mock_env(), true)
+            .unwrap();
+        assert!(legacy.may_load(deps.as_ref().storage).unwrap().is_none());
+
+        (owner, deps)
+    }
+
+    #[test]
+    fn migrate_from_cw_ownable_owned() {
+        let (owner, deps) = migrate_cw_ownable(CwOwnableOwnership {
+            owner: Some(Addr::unchecked("peter_parker")),
+            pending_owner: None,
+            pending_expiry: None,
+        });
+
+        let storage = deps.as_ref().storage;
+        let state = owner.state(storage).unwrap();
+        match state {
+            OwnerState::Base { .. } => {}
+            _ => panic!("Should be in the Base state"),
+        }
+        assert!(owner
+            .is_owner(storage, &Addr::unchecked("peter_parker"))
+            .unwrap());
+    }
+
+    #[test]
+    fn migrate_from_cw_ownable_pending_transfer() {
+        let expiry = Expiration::AtHeight(mock_env().block.height + 100);
+        let (owner, mut deps) = migrate_cw_ownable(CwOwnableOwnership {
+            owner: Some(Addr::unchecked("peter_parker")),
+            pending_owner: Some(Addr::unchecked("miles_morales")),
+            pending_expiry: Some(expiry),
+        });
+
+        let storage = deps.as_ref().storage;
+        let state = owner.state(storage).unwrap();
+        match state {
+            OwnerState::Proposed { .. } => {}
+            _ => panic!("Should be in the Proposed state"),
+        }
+        let res = owner.query(storage).unwrap();
+        assert_eq!(res.owner, Some("peter_parker".to_string()));
+        assert_eq!(res.proposed, Some("miles_morales".to_string()));
+        assert_eq!(res.proposed_expiry, Some(expiry));
+
+        // The pending transfer can be completed after migration
+        owner
+            .update::<Empty, Empty>(
+                deps.as_mut(),
+                mock_env(),
+                mock_info("miles_morales", &[]),
+                CwOwnableAction::AcceptOwnership.into(),
+            )
+            .unwrap();
+        assert!(owner
+            .is_owner(deps.as_ref().storage, &Addr::unchecked("miles_morales"))
+            .unwrap());
+    }
+
+    #[test]
+    fn migrate_from_cw_ownable_pending_transfer_without_expiry() {
+        let (owner, deps) = migrate_cw_ownable(CwOwnableOwnership {
+            owner: Some(Addr::unchecked("peter_parker")),
+            pending_owner: Some(Addr::unchecked("miles_morales")),
+            pending_expiry: None,
+        });
+
+        let res = owner.query(deps.as_ref().storage).unwrap();
+        assert_eq!(res.proposed, Some("miles_morales".to_string()));
+        assert_eq!(res.proposed_expiry, None);
+    }
+
+    #[test]
+    fn migrate_from_cw_ownable_renounced() {
+        let (owner, deps) = migrate_cw_ownable(CwOwnableOwnership {
+            owner: None,
+            pending_owner: None,
+            pending_expiry: None,
+        });
+
+        let storage = deps.as_ref().storage;
+        let state = owner.state(storage).unwrap();
+        match state {
+            OwnerState::Abolished => {}
+            _ => panic!("Should be in the Abolished state"),
+        }
+    }
+
+    #[test]
+    fn invalid_migrate_from_cw_ownable() {
+        let mut deps = mock_dependencies();
+        let owner = Owner::new("xyz");
+
+        let mut_deps = deps.as_mut();
+        let err = owner
+            .migrate_from_cw_ownable(mut_deps.storage, mut_deps.api, &mock_env(), true)
+            .unwrap_err();
+        assert_eq!(
+            err,
+            OwnerError::LegacyOwnerNotFound {
+                key: "ownership".to_string()
+            }
+        );
+
+        let legacy: Item<CwOwnableOwnership> = Item::new("ownership");
+        legacy
+            .save(
+                mut_deps.storage,
+                &CwOwnableOwnership {
+                    owner: None,
+                    pending_owner: Some(Addr::unchecked("miles_morales")),
+                    pending_expiry: None,
+                },
+            )
+            .unwrap();
+        let err = owner
+            .migrate_from_cw_ownable(mut_deps.storage, mut_deps.api, &mock_env(), true)
+            .unwrap_err();
+        assert_eq!(err, OwnerError::InvalidLegacyOwnership {});
+    }
+
+    #[test]
+    fn cw_ownable_actions_into_owner_updates() {
+        let expiry = Some(Expiration::AtHeight(100));
+        assert_eq!(
+            OwnerUpdate::from(CwOwnableAction::TransferOwnership {
+                new_owner: "miles_morales".to_string(),
+                expiry,
+            }),
+            ProposeNewOwner {
+                proposed: "miles_morales".to_string(),
+                expiry,
+            }
+        );
+        assert_eq!(
+            OwnerUpdate::from(CwOwnableAction::AcceptOwnership),
+            AcceptProposed
+        );
+        assert_eq!(
+            OwnerUpdate::from(CwOwnableAction::RenounceOwnership),
+            AbolishOwnerRole
+        );
+    }
+
+    //--------------------------------------------------------------------------------------------------
+    // Test ownership history
+    //--------------------------------------------------------------------------------------------------
+
+    #[test]
+    fn history_is_off_by_default() {
+        let mut deps = mock_dependencies();
+        let owner = Owner::new("xyz");
+        let mut_deps = deps.as_mut();
+        owner
+            .initialize(
+                mut_deps.storage,
+                mut_deps.api,
+                OwnerInit::SetInitialOwner {
+                    owner: "peter_parker".to_string(),
+                },
+            )
+            .unwrap();
+
+        owner
+            .update::<Empty, Empty>(
+                deps.as_mut(),
+                mock_env(),
+                mock_info("peter_parker", &[]),
+                ProposeNewOwner {
+                    proposed: "miles_morales".to_string(),
+                    expiry: None,
+                },
+            )
+            .unwrap();
+
+        let history = owner.history(deps.as_ref().storage, None, None).unwrap();
+        assert_eq!(history, vec![]);
+    }
+
+    #[test]
+    fn history_records_successful_updates() {
+        let mut deps = mock_dependencies();
+        let owner = Owner::new("xyz").with_history();
+        let mut_deps = deps.as_mut();
+        owner
+            .initialize_with_env(
+                mut_deps.storage,
+                mut_deps.api,
+                &mock_env(),
+                OwnerInit::SetInitialOwner {
+                    owner: "peter_parker".to_string(),
+                },
+            )
+            .unwrap();
+
+        owner
+            .update::<Empty, Empty>(
+                deps.as_mut(),
+                mock_env(),
+                mock_info("peter_parker", &[]),
+                ProposeNewOwner {
+                    proposed: "miles_morales".to_string(),
+                    expiry: None,
+                },
+            )
+            .unwrap();
+
+        // Failed updates are not recorded
+        owner
+            .update::<Empty, Empty>(
+                deps.as_mut(),
+                mock_env(),
+                mock_info("doc_oc", &[]),
+                AcceptProposed,
+            )
+            .unwrap_err();
+
+        let mut env = mock_env();
+        env.block.height += 10;
+        env.block.time = env.block.time.plus_seconds(60);
+        owner
+            .update::<Empty, Empty>(
+                deps.as_mut(),
+                env.clone(),
+                mock_info("miles_morales", &[]),
+                AcceptProposed,
+            )
+            .unwrap();
+
+        let history = owner.history(deps.as_ref().storage, None, None).unwrap();
+        assert_eq!(
+            history,
+            vec![
+                OwnerHistoryRecord {
+                    id: 0,
+                    action: "set_initial_owner".to_string(),
+                    sender: mock_env().contract.address,
+                    previous_owner: None,
+                    owner: Some(Addr::unchecked("peter_parker")),
+                    proposed: None,
+                    height: mock_env().block.height,
+                    time: mock_env().block.time,
+                },
+                OwnerHistoryRecord {
+                    id: 1,
+                    action: "propose_new_owner".to_string(),
+                    sender: Addr::unchecked("peter_parker"),
+                    previous_owner: Some(Addr::unchecked("peter_parker")),
+                    owner: Some(Addr::unchecked("peter_parker")),
+                    proposed: Some(Addr::unchecked("miles_morales")),
+                    height: mock_env().block.height,
+                    time: mock_env().block.time,
+                },
+                OwnerHistoryRecord {
+                    id: 2,
+                    action: "accept_proposed".to_string(),
+                    sender: Addr::unchecked("miles_morales"),
+                    previous_owner: Some(Addr::unchecked("peter_parker")),
+                    owner: Some(Addr::unchecked("miles_morales")),
+                    proposed: None,
+                    height: env.block.height,
+                    time: env.block.time,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn history_is_paginated() {
+        let mut deps = mock_dependencies();
+        let owner = Owner::new("xyz").with_history();
+        let mut_deps = deps.as_mut();
+        owner
+            .initialize_with_env(
+                mut_deps.storage,
+                mut_deps.api,
+                &mock_env(),
+                OwnerInit::SetInitialOwner {
+                    owner: "peter_parker".to_string(),
+                },
+            )
+            .unwrap();
+
+        for _ in 0..20 {
+            owner
+                .update::<Empty, Empty>(
+                    deps.as_mut(),
+                    mock_env(),
+                    mock_info("peter_parker", &[]),
+                    ProposeNewOwner {
+                        proposed: "miles_morales".to_string(),
+                        expiry: None,
+                    },
+                )
+                .unwrap();
+            owner
+                .update::<Empty, Empty>(
+                    deps.as_mut(),
+                    mock_env(),
+                    mock_info("peter_parker", &[]),
+                    ClearProposed,
+                )
+                .unwrap();
+        }
+
+        let storage = deps.as_ref().storage;
+        let history = owner.history(storage, None, None).unwrap();
+        assert_eq!(history.len(), 10);
+        assert_eq!(history[0].action, "set_initial_owner");
+        assert_eq!(history[9].id, 9);
+
+        let history = owner.history(storage, Some(9), Some(100)).unwrap();
+        assert_eq!(history.len(), 30);
+        assert_eq!(history[0].id, 10);
+        assert_eq!(history[0].action, "clear_proposed");
+        assert_eq!(history[29].id, 39);
+        assert_eq!(history[29].action, "propose_new_owner");
+
+        let history = owner.history(storage, Some(39), None).unwrap();
+        assert_eq!(history.len(), 1);
+        assert_eq!(history[0].action, "clear_proposed");
+    }
+
+    #[test]
+    fn history_requires_env_to_initialize() {
+        let mut deps = mock_dependencies();
+        let owner = Owner::new("xyz").with_history();
+        let mut_deps = deps.as_mut();
+        let err = owner
+            .initialize(
+                mut_deps.storage,
+                mut_deps.api,
+                OwnerInit::SetInitialOwner {
+                    owner: "peter_parker".to_string(),
+                },
+            )
+            .unwrap_err();
+        assert_eq!(err, OwnerError::EnvRequired {});
+        assert!(!owner.query(mut_deps.storage).unwrap().initialized);
+    }
+
+    #[test]
+    fn history_records_migration() {
+        let mut deps = mock_dependencies();
+        let owner = Owner::new("owner").with_history();
+
+        let legacy: Item<Addr> = Item::new("owner");
+        legacy
+            .save(deps.as_mut().storage, &Addr::unchecked("peter_parker"))
+            .unwrap();
+
+        let mut_deps = deps.as_mut();
+        owner
+            .migrate_from_addr_item(mut_deps.storage, mut_deps.api, &mock_env(), "owner", true)
+            .unwrap();
+
+        let history = owner.history(deps.as_ref().storage, None, None).unwrap();
+        assert_eq!(
+            history,
+            vec![OwnerHistoryRecord {
+                id: 0,
+                action: "migrate".to_string(),
+                sender: mock_env().contract.address,
+                previous_owner: None,
+                owner: Some(Addr::unchecked("peter_parker")),
+                proposed: None,
+                height: mock_env().block.height,
+                time: mock_env().block.time,
+            }]
+        );
+    }
+
+    #[test]
+    fn history_skips_pending_approvals() {
+        let mut deps = mock_dependencies();
+        let owner = Owner::new("xyz").with_history();
+        let mut_deps = deps.as_mut();
+        owner
+            .initialize_with_env(
+                mut_deps.storage,
+                mut_deps.api,
+                &mock_env(),
+                OwnerInit::SetInitialOwners {
+                    owners: vec!["peter_parker".to_string(), "miles_morales".to_string()],
+                    threshold: 2,
+                },
+            )
+            .unwrap();
+
+        let withdraw = WithdrawApproval {
+            action: "\"abolish_owner_role\"".to_string(),
+        };
+        for update in [AbolishOwnerRole, withdraw, AbolishOwnerRole] {
+            owner
+                .update::<Empty, Empty>(
+                    deps.as_mut(),
+                    mock_env(),
+                    mock_info("peter_parker", &[]),
+                    update,
+                )
+                .unwrap();
+        }
+        let history = owner.history(deps.as_ref().storage, None, None).unwrap();
+        assert_eq!(history.len(), 1);
+
+        owner
+            .update::<Empty, Empty>(
+                deps.as_mut(),
+                mock_env(),
+                mock_info("miles_morales", &[]),
+                AbolishOwnerRole,
+            )
+            .unwrap();
+        let history = owner.history(deps.as_ref().storage, None, None).unwrap();
+        assert_eq!(history.len(), 2);
+        assert_eq!(history[1].action, "abolish_owner_role");
+        assert_eq!(history[1].sender, Addr::unchecked("miles_morales"));
+    }
+
+    //--------------------------------------------------------------------------------------------------
+    // Test snapshots
+    //--------------------------------------------------------------------------------------------------
+
+    fn env_at_height(height: u64) -> Env {
+        let mut env = mock_env();
+        env.block.height = height;
+        env
+    }
+
+    #[test]
+    fn current_at_height() {
+        let mut deps = mock_dependencies();
+        let owner = Owner::new("xyz").with_snapshots();
+        let mut_deps = deps.as_mut();
+        let err = owner
+            .initialize(
+                mut_deps.storage,
+                mut_deps.api,
+                OwnerInit::SetInitialOwner {
+                    owner: "peter_parker".to_string(),
+                },
+            )
+            .unwrap_err();
+        assert_eq!(err, OwnerError::EnvRequired {});
+
+        owner
+            .initialize_with_env(
+                mut_deps.storage,
+                mut_deps.api,
+                &env_at_height(10),
+                OwnerInit::SetInitialOwner {
+                    owner: "peter_parker".to_string(),
+                },
+            )
+            .unwrap();
+
+        owner
+            .update::<Empty, Empty>(
+                deps.as_mut(),
+                env_at_height(100),
+                mock_info("peter_parker", &[]),
+                ProposeNewOwner {
+                    proposed: "miles_morales".to_string(),
+                    expiry: None,
+                },
+            )
+            .unwrap();
+        owner
+            .update::<Empty, Empty>(
+                deps.as_mut(),
+                env_at_height(200),
+                mock_info("miles_morales", &[]),
+                AcceptProposed,
+            )
+            .unwrap();
+
+        let storage = deps.as_ref().storage;
+        let peter = Some(Addr::unchecked("peter_parker"));
+        let miles = Some(Addr::unchecked("miles_morales"));
+        assert_eq!(owner.current_at_height(storage, 5).unwrap(), None);
+        assert_eq!(owner.current_at_height(storage, 10).unwrap(), None);
+        assert_eq!(owner.current_at_height(storage, 50).unwrap(), peter);
+        assert_eq!(owner.current_at_height(storage, 150).unwrap(), peter);
+        assert_eq!(owner.current_at_height(storage, 200).unwrap(), peter);
+        assert_eq!(owner.current_at_height(storage, 201).unwrap(), miles);
+        assert_eq!(owner.current(storage).unwrap(), miles);
+
+        // Without snapshots past owners are unknown
+        let owner = Owner::new("xyz");
+        let err = owner.current_at_height(storage, 150).unwrap_err();
+        assert_eq!(err, StdError::generic_err("snapshots not enabled"));
+    }
+
+    #[test]
+    fn migration_is_snapshotted() {
+        let mut deps = mock_dependencies();
+        let owner = Owner::new("owner").with_snapshots();
+
+        let legacy: Item<Addr> = Item::new("owner");
+        legacy
+            .save(deps.as_mut().storage, &Addr::unchecked("peter_parker"))
+            .unwrap();
+
+        let mut_deps = deps.as_mut();
+        owner
+            .migrate_from_addr_item(
+                mut_deps.storage,
+                mut_deps.api,
+                &env_at_height(100),
+                "owner",
+                false,
+            )
+            .unwrap();
+
+        let storage = deps.as_ref().storage;
+        let peter = Some(Addr::unchecked("peter_parker"));
+        assert_eq!(owner.current_at_height(storage, 50).unwrap(), None);
+        assert_eq!(owner.current_at_height(storage, 101).unwrap(), peter);
+        assert_eq!(owner.current(storage).unwrap(), peter);
+    }
+
+    #[cfg(feature = "emergency-owner")]
+    #[test]
+    fn emergency_owner_at_height() {
+        let mut deps = mock_dependencies();
+        let owner = Owner::new("xyz").with_snapshots();
+        let mut_deps = deps.as_mut();
+        owner
+            .initialize_with_env(
+                mut_deps.storage,
+                mut_deps.api,
+                &env_at_height(10),
+                OwnerInit::SetInitialOwner {
+                    owner: "peter_parker".to_string(),
+                },
+            )
+            .unwrap();
+
+        owner
+            .update::<Empty, Empty>(
+                deps.as_mut(),
+                env_at_height(100),
+                mock_info("peter_parker", &[]),
+                SetEmergencyOwner {
+                    emergency_owner: "miles_morales".to_string(),
+                },
+            )
+            .unwrap();
+        owner
+            .update::<Empty, Empty>(
+                deps.as_mut(),
+                env_at_height(200),
+                mock_info("peter_parker", &[]),
+                ClearEmergencyOwner,
+            )
+            .unwrap();
+
+        let storage = deps.as_ref().storage;
+        let miles = Some(Addr::unchecked("miles_morales"));
+        assert_eq!(owner.emergency_owner_at_height(storage, 100).unwrap(), None);
+        assert_eq!(
+            owner.emergency_owner_at_height(storage, 101).unwrap(),
+            miles
+        );
+        assert_eq!(
+            owner.emergency_owner_at_height(storage, 200).unwrap(),
+            miles
+        );
+        assert_eq!(owner.emergency_owner_at_height(storage, 201).unwrap(), None);
+    }
+
+    //--------------------------------------------------------------------------------------------------
+    // Test events
+    //--------------------------------------------------------------------------------------------------
+
+    #[test]
+    fn update_emits_owner_event() {
+        let mut deps = mock_dependencies();
+        let owner = Owner::new("xyz");
+        let mut_deps = deps.as_mut();
+        owner
+            .initialize(
+                mut_deps.storage,
+                mut_deps.api,
+                OwnerInit::SetInitialOwner {
+                    owner: "peter_parker".to_string(),
+                },
+            )
+            .unwrap();
+
+        let res = owner
+            .update::<Empty, Empty>(
+                deps.as_mut(),
+                mock_env(),
+                mock_info("peter_parker", &[]),
+                ProposeNewOwner {
+                    proposed: "miles_morales".to_string(),
+                    expiry: None,
+                },
+            )
+            .unwrap();
+
+        assert_eq!(res.events.len(), 1);
+        assert_eq!(
+            OwnerEvent::try_from(&res.events[0]).unwrap(),
+            OwnerEvent {
+                namespace: "xyz".to_string(),
+                action: "propose_new_owner".to_string(),
+                sender: Addr::unchecked("peter_parker"),
+                previous_owner: Some(Addr::unchecked("peter_parker")),
+                owner: Some(Addr::unchecked("peter_parker")),
+                previous_proposed: None,
+                proposed: Some(Addr::unchecked("miles_morales")),
+                previous_proposed_expiry: None,
+                proposed_expiry: None,
+                previous_accept_after: None,
+                accept_after: Some(mock_env().block.time),
+                previous_abolish_after: None,
+                abolish_after: None,
+                previous_owners: vec![],
+                owners: vec![],
+                previous_threshold: None,
+                threshold: None,
+                #[cfg(feature = "emergency-owner")]
+                previous_emergency_owner: None,
+                #[cfg(feature = "emergency-owner")]
+                emergency_owner: None,
+            }
+        );
+    }
+
+    #[test]
+    fn multi_owner_event_describes_group() {
+        let mut deps = mock_dependencies();
+        let owner = Owner::new("xyz");
+        let mut_deps = deps.as_mut();
+        init_multi_owner(mut_deps.storage, mut_deps.api, &owner);
+
+        let expiry = Expiration::AtHeight(mock_env().block.height + 100);
+        let propose = ProposeNewOwner {
+            proposed: "doc_oc".to_string(),
+            expiry: Some(expiry),
+        };
+        let res = owner
+            .update::<Empty, Empty>(
+                deps.as_mut(),
+                mock_env(),
+                mock_info("peter_parker", &[]),
+                propose.clone(),
+            )
+            .unwrap();
+        assert!(res.events.is_empty());
+
+        let res = owner
+            .update::<Empty, Empty>(
+                deps.as_mut(),
+                mock_env(),
+                mock_info("miles_morales", &[]),
+                propose,
+            )
+            .unwrap();
+        let event = OwnerEvent::try_from(&res.events[0]).unwrap();
+        let owners = vec![
+            Addr::unchecked("peter_parker"),
+            Addr::unchecked("miles_morales"),
+            Addr::unchecked("gwen_stacy"),
+        ];
+        assert_eq!(event.previous_owners, owners);
+        assert_eq!(event.owners, owners);
+        assert_eq!(event.previous_threshold, Some(2));
+        assert_eq!(event.threshold, Some(2));
+
+        let res = owner.query(deps.as_ref().storage).unwrap();
+        assert_eq!(res.proposed, Some("doc_oc".to_string()));
+
+        let res = owner
+            .update::<Empty, Empty>(
+                deps.as_mut(),
+                mock_env(),
+                mock_info("doc_oc", &[]),
+                AcceptProposed,
+            )
+            .unwrap();
+        let event = OwnerEvent::try_from(&res.events[0]).unwrap();
+        assert_eq!(event.previous_proposed_expiry, Some(expiry));
+        assert_eq!(event.previous_accept_after, Some(mock_env().block.time));
+        assert_eq!(event.proposed_expiry, None);
+        assert_eq!(event.owner, Some(Addr::unchecked("doc_oc")));
+        assert!(event.owners.is_empty());
+        assert_eq!(event.threshold, None);
+    }
+
+    #[test]
+    fn responses_carry_label() {
+        let mut deps = mock_dependencies();
+        let owner = Owner::new("owner");
+        let risk_owner = Owner::new("risk_owner").with_label("risk");
+        for o in [&owner, &risk_owner] {
+            let mut_deps = deps.as_mut();
+            o.initialize(
+                mut_deps.storage,
+                mut_deps.api,
+                OwnerInit::SetInitialOwner {
+                    owner: "peter_parker".to_string(),
+                },
+            )
+            .unwrap();
+        }
+
+        for (o, label) in [(&owner, "owner"), (&risk_owner, "risk")] {
+            let res = o
+                .update::<Empty, Empty>(
+                    deps.as_mut(),
+                    mock_env(),
+                    mock_info("peter_parker", &[]),
+                    ProposeNewOwner {
+                        proposed: "miles_morales".to_string(),
+                        expiry: None,
+                    },
+                )
+                .unwrap();
+            assert_eq!(res.attributes[1].key, "label");
+            assert_eq!(res.attributes[1].value, label);
+            assert_eq!(o.query(deps.as_ref().storage).unwrap().label, label);
+        }
+    }
+
+    #[cfg(feature = "emergency-owner")]
+    #[test]
+    fn resign_emits_owner_event() {
+        let mut deps = mock_dependencies();
+        let owner = Owner::new("xyz");
+        let mut_deps = deps.as_mut();
+        owner
+            .initialize(
+                mut_deps.storage,
+                mut_deps.api,
+                OwnerInit::SetInitialOwner {
+                    owner: "peter_parker".to_string(),
+                },
+            )
+            .unwrap();
+        owner
+            .update::<Empty, Empty>(
+                deps.as_mut(),
+                mock_env(),
+                mock_info("peter_parker", &[]),
+                SetEmergencyOwner {
+                    emergency_owner: "miles_morales".to_string(),
+                },
+            )
+            .unwrap();
+
+        let res = owner
+            .update::<Empty, Empty>(
+                deps.as_mut(),
+                mock_env(),
+                mock_info("miles_morales", &[]),
+                ResignEmergencyOwner,
+            )
+            .unwrap();
+
+        let event = OwnerEvent::try_from(&res.events[0]).unwrap();
+        assert_eq!(event.action, "resign_emergency_owner");
+        assert_eq!(
+            event.previous_emergency_owner,
+            Some(Addr::unchecked("miles_morales"))
+        );
+        assert_eq!(event.emergency_owner, None);
+    }
+
+    //--------------------------------------------------------------------------------------------------
+    // Test message macros
+    //--------------------------------------------------------------------------------------------------
+
+    #[owner_execute]
+    #[cw_serde]
+    enum ExecuteMsg {
+        Deposit {},
+    }
+
+    #[derive(Debug, PartialEq)]
+    enum ContractError {
+        Owner(OwnerError),
+    }
+
+    impl From<OwnerError> for ContractError {
+        fn from(err: OwnerError) -> Self {
+            ContractError::Owner(err)
+        }
+    }
+
+    fn execute(
+        deps: DepsMut,
+        info: MessageInfo,
+        msg: ExecuteMsg,
+    ) -> Result<Response<Empty>, ContractError> {
+        let owner = Owner::new("xyz");
+        match msg {
+            ExecuteMsg::Deposit {} => Ok(Response::new()),
+            ExecuteMsg::UpdateOwner(update) => owner.execute_update(deps, mock_env(), info, update),
+        }
+    }
+
+    #[test]
+    fn owner_execute_adds_update_variant() {
+        let mut deps = mock_dependencies();
+        let mut_deps = deps.as_mut();
+        Owner::new("xyz")
+            .initialize(
+                mut_deps.storage,
+                mut_deps.api,
+                OwnerInit::SetInitialOwner {
+                    owner: "peter_parker".to_string(),
+                },
+            )
+            .unwrap();
 
-#[cfg(test)]
-mod tests {
+        let msg: ExecuteMsg =
+            from_slice(br#"{"update_owner":{"propose_new_owner":{"proposed":"miles_morales"}}}"#)
+                .unwrap();
+        assert_eq!(
+            msg,
+            ExecuteMsg::UpdateOwner(ProposeNewOwner {
+                proposed: "miles_morales".to_string(),
+                expiry: None,
+            })
+        );
 
-    //--------------------------------------------------------------------------------------------------
-    // Test invalid state transitions
-    //--------------------------------------------------------------------------------------------------
+        let err = execute(deps.as_mut(), mock_info("doc_oc", &[]), msg.clone()).unwrap_err();
+        assert!(matches!(
+            err,
+            ContractError::Owner(OwnerError::NotOwner { .. })
+        ));
 
-    use crate::owner::OwnerState;
-    use crate::OwnerUpdate::{AbolishOwnerRole, AcceptProposed, ClearProposed, ProposeNewOwner};
-    #[cfg(feature = "emergency-owner")]
-    use crate::OwnerUpdate::{ClearEmergencyOwner, SetEmergencyOwner};
-    use crate::{Owner, OwnerError, OwnerInit, OwnerResponse};
-    use cosmwasm_std::testing::{mock_dependencies, mock_info};
-    use cosmwasm_std::{Addr, Empty, Storage};
+        execute(deps.as_mut(), mock_info("peter_parker", &[]), msg).unwrap();
+        let proposed = Owner::new("xyz").proposed(deps.as_ref().storage).unwrap();
+        assert_eq!(proposed, Some(Addr::unchecked("miles_morales")));
+    }
+
+    #[owner_query]
+    #[cw_serde]
+    #[derive(QueryResponses)]
+    enum QueryMsg {
+        #[returns(Empty)]
+        Config {},
+    }
+
+    fn query(deps: Deps, msg: QueryMsg) -> StdResult<Binary> {
+        match msg {
+            QueryMsg::Config {} => to_binary(&Empty {}),
+            QueryMsg::Owner {} => Owner::new("xyz").query_binary(deps.storage),
+        }
+    }
 
     #[test]
-    fn invalid_uninitialized_state_transitions() {
+    fn owner_query_adds_owner_variant() {
         let mut deps = mock_dependencies();
-        let sender = Addr::unchecked("peter_parker");
-        let info = mock_info(sender.as_ref(), &[]);
-        let owner = Owner::new("xyz");
-
-        let err = owner
-            .update::<Empty, Empty>(
-                deps.as_mut(),
-                info.clone(),
-                ProposeNewOwner {
-                    proposed: "abc".to_string(),
+        let mut_deps = deps.as_mut();
+        Owner::new("xyz")
+            .initialize(
+                mut_deps.storage,
+                mut_deps.api,
+                OwnerInit::SetInitialOwner {
+                    owner: "peter_parker".to_string(),
                 },
             )
-            .unwrap_err();
-        assert_eq!(err, OwnerError::StateTransitionError {});
+            .unwrap();
 
-        let err = owner
-            .update::<Empty, Empty>(deps.as_mut(), info.clone(), ClearProposed)
-            .unwrap_err();
-        assert_eq!(err, OwnerError::StateTransitionError {});
+        let msg: QueryMsg = from_slice(br#"{"owner":{}}"#).unwrap();
+        assert_eq!(msg, QueryMsg::Owner {});
 
-        let err = owner
-            .update::<Empty, Empty>(deps.as_mut(), info.clone(), AcceptProposed)
-            .unwrap_err();
-        assert_eq!(err, OwnerError::StateTransitionError {});
+        let res: OwnerResponse = from_binary(&query(deps.as_ref(), msg).unwrap()).unwrap();
+        assert_eq!(res.owner, Some("peter_parker".to_string()));
+    }
 
-        let err = owner
-            .update::<Empty, Empty>(deps.as_mut(), info.clone(), AbolishOwnerRole)
-            .unwrap_err();
-        assert_eq!(err, OwnerError::StateTransitionError {});
+    const GUARDED: Owner = Owner::new("xyz");
+
+    #[only_owner(GUARDED)]
+    fn owner_handler(deps: DepsMut, info: MessageInfo) -> Result<Response, ContractError> {
+        Ok(Response::new().add_attribute("caller", info.sender))
+    }
+
+    #[cfg(feature = "emergency-owner")]
+    #[only_emergency_owner(GUARDED)]
+    fn emergency_handler(deps: DepsMut, info: &MessageInfo) -> Result<Response, ContractError> {
+        Ok(Response::new().add_attribute("caller", info.sender.clone()))
+    }
+
+    #[test]
+    fn only_owner_guards_handler() {
+        let mut deps = mock_dependencies();
+        let mut_deps = deps.as_mut();
+        GUARDED
+            .initialize(
+                mut_deps.storage,
+                mut_deps.api,
+                OwnerInit::SetInitialOwner {
+                    owner: "peter_parker".to_string(),
+                },
+            )
+            .unwrap();
+
+        let err = owner_handler(deps.as_mut(), mock_info("doc_oc", &[])).unwrap_err();
+        assert!(matches!(
+            err,
+            ContractError::Owner(OwnerError::NotOwner { .. })
+        ));
+        owner_handler(deps.as_mut(), mock_info("peter_parker", &[])).unwrap();
 
         #[cfg(feature = "emergency-owner")]
         {
-            let err = owner
+            GUARDED
                 .update::<Empty, Empty>(
                     deps.as_mut(),
-                    info.clone(),
+                    mock_env(),
+                    mock_info("peter_parker", &[]),
                     SetEmergencyOwner {
-                        emergency_owner: "xyz".to_string(),
+                        emergency_owner: "miles_morales".to_string(),
                     },
                 )
-                .unwrap_err();
+                .unwrap();
 
-            assert_eq!(err, OwnerError::StateTransitionError {});
-            let err = owner
-                .update::<Empty, Empty>(deps.as_mut(), info, ClearEmergencyOwner)
-                .unwrap_err();
-            assert_eq!(err, OwnerError::StateTransitionError {});
+            let err =
+                emergency_handler(deps.as_mut(), &mock_info("peter_parker", &[])).unwrap_err();
+            assert_eq!(err, ContractError::Owner(OwnerError::NotEmergencyOwner {}));
+            emergency_handler(deps.as_mut(), &mock_info("miles_morales", &[])).unwrap();
         }
     }
 
+    //--------------------------------------------------------------------------------------------------
+    // Test authorization tokens
+    //--------------------------------------------------------------------------------------------------
+
+    fn privileged(_auth: &OwnerAuth) -> &'static str {
+        "done"
+    }
+
     #[test]
-    fn invalid_owner_set_no_proposed_state_transitions() {
+    fn authorize_owner_hands_out_token() {
         let mut deps = mock_dependencies();
-        let sender = Addr::unchecked("peter_parker");
-        let info = mock_info(sender.as_ref(), &[]);
         let owner = Owner::new("xyz");
-
         let mut_deps = deps.as_mut();
-
         owner
             .initialize(
                 mut_deps.storage,
                 mut_deps.api,
                 OwnerInit::SetInitialOwner {
-                    owner: sender.to_string(),
+                    owner: "peter_parker".to_string(),
                 },
             )
             .unwrap();
 
+        let storage = deps.as_ref().storage;
         let err = owner
-            .initialize(
-                mut_deps.storage,
-                mut_deps.api,
-                OwnerInit::SetInitialOwner {
-                    owner: "abc".to_string(),
-                },
-            )
-            .unwrap_err();
-        assert_eq!(err, OwnerError::StateTransitionError {});
-
-        let err = owner
-            .update::<Empty, Empty>(deps.as_mut(), info.clone(), ClearProposed)
+            .authorize_owner(storage, &Addr::unchecked("doc_oc"))
             .unwrap_err();
-        assert_eq!(err, OwnerError::StateTransitionError {});
+        assert!(matches!(err, OwnerError::NotOwner { .. }));
 
-        let err = owner
-            .update::<Empty, Empty>(deps.as_mut(), info, AcceptProposed)
-            .unwrap_err();
-        assert_eq!(err, OwnerError::StateTransitionError {});
+        let auth = owner
+            .authorize_owner(storage, &Addr::unchecked("peter_parker"))
+            .unwrap();
+        assert_eq!(std::mem::size_of_val(&auth), 0);
+        assert_eq!(privileged(&auth), "done");
     }
 
+    #[cfg(feature = "emergency-owner")]
     #[test]
-    fn invalid_owner_set_with_proposed_state_transitions() {
+    fn authorize_emergency_owner_hands_out_token() {
         let mut deps = mock_dependencies();
-        let sender = Addr::unchecked("peter_parker");
-        let info = mock_info(sender.as_ref(), &[]);
         let owner = Owner::new("xyz");
-
         let mut_deps = deps.as_mut();
-
         owner
             .initialize(
                 mut_deps.storage,
                 mut_deps.api,
                 OwnerInit::SetInitialOwner {
-                    owner: sender.to_string(),
+                    owner: "peter_parker".to_string(),
                 },
             )
             .unwrap();
-
         owner
             .update::<Empty, Empty>(
-                mut_deps,
-                info.clone(),
-                ProposeNewOwner {
-                    proposed: "abc".to_string(),
+                deps.as_mut(),
+                mock_env(),
+                mock_info("peter_parker", &[]),
+                SetEmergencyOwner {
+                    emergency_owner: "miles_morales".to_string(),
                 },
             )
             .unwrap();
 
-        let mut_deps = deps.as_mut();
-
+        let storage = deps.as_ref().storage;
         let err = owner
+            .authorize_emergency_owner(storage, &Addr::unchecked("peter_parker"))
+            .unwrap_err();
+        assert_eq!(err, OwnerError::NotEmergencyOwner {});
+        owner
+            .authorize_emergency_owner(storage, &Addr::unchecked("miles_morales"))
+            .unwrap();
+    }
+
+    struct RiskOwner;
+
+    fn risk_privileged(_auth: &OwnerAuth<RiskOwner>) -> &'static str {
+        "risk"
+    }
+
+    #[test]
+    fn branded_owner_hands_out_own_tokens() {
+        const RISK_OWNER: Owner<RiskOwner> = Owner::new("risk_owner").branded();
+
+        let mut deps = mock_dependencies();
+        let mut_deps = deps.as_mut();
+        RISK_OWNER
             .initialize(
                 mut_deps.storage,
                 mut_deps.api,
                 OwnerInit::SetInitialOwner {
-                    owner: "abc".to_string(),
-                },
-            )
-            .unwrap_err();
-        assert_eq!(err, OwnerError::StateTransitionError {});
-
-        let err = owner
-            .update::<Empty, Empty>(
-                deps.as_mut(),
-                info.clone(),
-                ProposeNewOwner {
-                    proposed: "efg".to_string(),
+                    owner: "peter_parker".to_string(),
                 },
             )
-            .unwrap_err();
-        assert_eq!(err, OwnerError::StateTransitionError {});
+            .unwrap();
 
-        #[cfg(feature = "emergency-owner")]
-        {
-            let err = owner
-                .update::<Empty, Empty>(
-                    deps.as_mut(),
-                    info.clone(),
-                    SetEmergencyOwner {
-                        emergency_owner: "xyz".to_string(),
-                    },
-                )
-                .unwrap_err();
-            assert_eq!(err, OwnerError::StateTransitionError {});
+        let storage = deps.as_ref().storage;
+        let auth = RISK_OWNER
+            .authorize_owner(storage, &Addr::unchecked("peter_parker"))
+            .unwrap();
+        assert_eq!(std::mem::size_of_val(&auth), 0);
+        assert_eq!(risk_privileged(&auth), "risk");
 
-            let err = owner
-                .update::<Empty, Empty>(deps.as_mut(), info, ClearEmergencyOwner)
-                .unwrap_err();
-            assert_eq!(err, OwnerError::StateTransitionError {});
-        }
+        // Tokens of unbranded Owners are a different type
+        assert_ne!(
+            TypeId::of::<OwnerAuth<RiskOwner>>(),
+            TypeId::of::<OwnerAuth>()
+        );
     }
 
+    //--------------------------------------------------------------------------------------------------
+    // Test hooks
+    //--------------------------------------------------------------------------------------------------
+
     #[test]
-    fn invalid_owner_role_abolished_state_transitions() {
+    fn hooks_are_notified_of_updates() {
         let mut deps = mock_dependencies();
-        let sender = Addr::unchecked("peter_parker");
-        let info = mock_info(sender.as_ref(), &[]);
-        let owner = Owner::new("xyz");
+        let owner = Owner::new("xyz").with_hooks();
+        let peter = Addr::unchecked("peter_parker");
+        let miles = Addr::unchecked("miles_morales");
 
         let mut_deps = deps.as_mut();
-
         owner
-            .initialize(mut_deps.storage, mut_deps.api, OwnerInit::AbolishOwnerRole)
-            .unwrap();
-
-        let err = owner
             .initialize(
                 mut_deps.storage,
                 mut_deps.api,
                 OwnerInit::SetInitialOwner {
-                    owner: "abc".to_string(),
+                    owner: peter.to_string(),
                 },
             )
-            .unwrap_err();
-        assert_eq!(err, OwnerError::StateTransitionError {});
+            .unwrap();
 
         let err = owner
-            .update::<Empty, Empty>(
+            .update_hooks::<Empty, Empty>(
                 deps.as_mut(),
-                info.clone(),
-                ProposeNewOwner {
-                    proposed: "efg".to_string(),
+                mock_info(miles.as_str(), &[]),
+                OwnerHooksUpdate::AddHook {
+                    addr: "oracle".to_string(),
                 },
             )
             .unwrap_err();
-        assert_eq!(err, OwnerError::StateTransitionError {});
+        assert_eq!(
+            err,
+            OwnerError::NotOwner {
+                expected: "peter_parker".to_string(),
+                actual: "miles_morales".to_string(),
+            }
+        );
 
+        for hook in ["oracle", "red_bank"] {
+            owner
+                .update_hooks::<Empty, Empty>(
+                    deps.as_mut(),
+                    mock_info(peter.as_str(), &[]),
+                    OwnerHooksUpdate::AddHook {
+                        addr: hook.to_string(),
+                    },
+                )
+                .unwrap();
+        }
         let err = owner
-            .update::<Empty, Empty>(deps.as_mut(), info.clone(), ClearProposed)
+            .update_hooks::<Empty, Empty>(
+                deps.as_mut(),
+                mock_info(peter.as_str(), &[]),
+                OwnerHooksUpdate::AddHook {
+                    addr: "oracle".to_string(),
+                },
+            )
             .unwrap_err();
-        assert_eq!(err, OwnerError::StateTransitionError {});
+        assert_eq!(
+            err,
+            OwnerError::HookAlreadyRegistered {
+                addr: "oracle".to_string()
+            }
+        );
+        assert_eq!(
+            owner.query_hooks(deps.as_ref().storage).unwrap(),
+            HooksResponse {
+                hooks: vec!["oracle".to_string(), "red_bank".to_string()]
+            }
+        );
 
-        let err = owner
-            .update::<Empty, Empty>(deps.as_mut(), info.clone(), AcceptProposed)
-            .unwrap_err();
-        assert_eq!(err, OwnerError::StateTransitionError {});
+        let res = owner
+            .update::<Empty, Empty>(
+                deps.as_mut(),
+                mock_env(),
+                mock_info(peter.as_str(), &[]),
+                ProposeNewOwner {
+                    proposed: miles.to_string(),
+                    expiry: None,
+                },
+            )
+            .unwrap();
+        let hook = OwnerHookMsg::OwnerChanged(OwnerChangedHook {
+            namespace: "xyz".to_string(),
+            action: "propose_new_owner".to_string(),
+            sender: peter.clone(),
+            previous_owner: Some(peter.clone()),
+            owner: Some(peter.clone()),
+            proposed: Some(miles.clone()),
+            #[cfg(feature = "emergency-owner")]
+            emergency_owner: None,
+        });
+        let expected: Vec<CosmosMsg> = ["oracle", "red_bank"]
+            .into_iter()
+            .map(|addr| {
+                WasmMsg::Execute {
+                    contract_addr: addr.to_string(),
+                    msg: to_binary(&hook).unwrap(),
+                    funds: vec![],
+                }
+                .into()
+            })
+            .collect();
+        let msgs: Vec<CosmosMsg> = res.messages.into_iter().map(|sub| sub.msg).collect();
+        assert_eq!(msgs, expected);
 
+        owner
+            .update_hooks::<Empty, Empty>(
+                deps.as_mut(),
+                mock_info(peter.as_str(), &[]),
+                OwnerHooksUpdate::RemoveHook {
+                    addr: "oracle".to_string(),
+                },
+            )
+            .unwrap();
         let err = owner
-            .update::<Empty, Empty>(deps.as_mut(), info.clone(), AbolishOwnerRole)
+            .update_hooks::<Empty, Empty>(
+                deps.as_mut(),
+                mock_info(peter.as_str(), &[]),
+                OwnerHooksUpdate::RemoveHook {
+                    addr: "oracle".to_string(),
+                },
+            )
             .unwrap_err();
-        assert_eq!(err, OwnerError::StateTransitionError {});
-
-        #[cfg(feature = "emergency-owner")]
-        {
-            let err = owner
-                .update::<Empty, Empty>(
-                    deps.as_mut(),
-                    info.clone(),
-                    SetEmergencyOwner {
-                        emergency_owner: "xyz".to_string(),
-                    },
-                )
-                .unwrap_err();
-            assert_eq!(err, OwnerError::StateTransitionError {});
+        assert_eq!(
+            err,
+            OwnerError::HookNotRegistered {
+                addr: "oracle".to_string()
+            }
+        );
 
-            let err = owner
-                .update::<Empty, Empty>(deps.as_mut(), info, ClearEmergencyOwner)
-                .unwrap_err();
-            assert_eq!(err, OwnerError::StateTransitionError {});
+        let res = owner
+            .update::<Empty, Empty>(
+                deps.as_mut(),
+                mock_env(),
+                mock_info(miles.as_str(), &[]),
+                AcceptProposed,
+            )
+            .unwrap();
+        assert_eq!(res.messages.len(), 1);
+        match &res.messages[0].msg {
+            CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr, msg, ..
+            }) => {
+                assert_eq!(contract_addr, "red_bank");
+                let OwnerHookMsg::OwnerChanged(hook) = from_binary(msg).unwrap();
+                assert_eq!(hook.action, "accept_proposed");
+                assert_eq!(hook.previous_owner, Some(peter));
+                assert_eq!(hook.owner, Some(miles));
+            }
+            msg => panic!("Unexpected message {:?}", msg),
         }
     }
 
-    //--------------------------------------------------------------------------------------------------
-    // Test permissions
-    //--------------------------------------------------------------------------------------------------
-
     #[test]
-    fn initialize_owner_permissions() {
+    fn multi_owner_hook_approvals() {
         let mut deps = mock_dependencies();
+        let owner = Owner::new("xyz").with_hooks();
         let mut_deps = deps.as_mut();
-        let owner = Owner::new("xyz");
+        init_multi_owner(mut_deps.storage, mut_deps.api, &owner);
 
-        // Anyone can initialize
-        owner
-            .initialize(mut_deps.storage, mut_deps.api, OwnerInit::AbolishOwnerRole)
+        let add = OwnerHooksUpdate::AddHook {
+            addr: "oracle".to_string(),
+        };
+        let res = owner
+            .update_hooks::<Empty, Empty>(
+                deps.as_mut(),
+                mock_info("peter_parker", &[]),
+                add.clone(),
+            )
             .unwrap();
+        assert_eq!(res.attributes[1].value, "pending_approval");
 
-        let mut deps = mock_dependencies();
-        let mut_deps = deps.as_mut();
+        let multi = owner.multi_owner(deps.as_ref().storage).unwrap().unwrap();
+        assert_eq!(
+            multi.pending[0].action,
+            "xyz_hooks:{\"add_hook\":{\"addr\":\"oracle\"}}"
+        );
 
         owner
-            .initialize(
-                mut_deps.storage,
-                mut_deps.api,
-                OwnerInit::SetInitialOwner {
-                    owner: "xyz".to_string(),
-                },
+            .update_hooks::<Empty, Empty>(deps.as_mut(), mock_info("gwen_stacy", &[]), add)
+            .unwrap();
+        assert_eq!(
+            owner.hooks(deps.as_ref().storage).unwrap(),
+            vec![Addr::unchecked("oracle")]
+        );
+
+        // Hooks only hear of owner updates once they went through
+        let propose = ProposeNewOwner {
+            proposed: "doc_oc".to_string(),
+            expiry: None,
+        };
+        let res = owner
+            .update::<Empty, Empty>(
+                deps.as_mut(),
+                mock_env(),
+                mock_info("peter_parker", &[]),
+                propose.clone(),
+            )
+            .unwrap();
+        assert!(res.messages.is_empty());
+
+        let res = owner
+            .update::<Empty, Empty>(
+                deps.as_mut(),
+                mock_env(),
+                mock_info("gwen_stacy", &[]),
+                propose,
             )
             .unwrap();
+        assert_eq!(res.messages.len(), 1);
     }
 
     #[test]
-    fn propose_new_owner_permissions() {
+    fn hooks_require_opt_in() {
         let mut deps = mock_dependencies();
-        let sender = Addr::unchecked("peter_parker");
         let owner = Owner::new("xyz");
 
         let mut_deps = deps.as_mut();
@@ -639,32 +4784,57 @@ mod tests {
                 mut_deps.storage,
                 mut_deps.api,
                 OwnerInit::SetInitialOwner {
-                    owner: sender.to_string(),
+                    owner: "peter_parker".to_string(),
                 },
             )
             .unwrap();
 
-        let bad_guy = Addr::unchecked("doc_oc");
-        let info = mock_info(bad_guy.as_ref(), &[]);
         let err = owner
-            .update::<Empty, Empty>(
-                mut_deps,
-                info,
-                ProposeNewOwner {
-                    proposed: bad_guy.to_string(),
+            .update_hooks::<Empty, Empty>(
+                deps.as_mut(),
+                mock_info("peter_parker", &[]),
+                OwnerHooksUpdate::AddHook {
+                    addr: "oracle".to_string(),
                 },
             )
             .unwrap_err();
+        assert_eq!(err, OwnerError::HooksDisabled {});
+
+        let res = owner
+            .update::<Empty, Empty>(
+                deps.as_mut(),
+                mock_env(),
+                mock_info("peter_parker", &[]),
+                AbolishOwnerRole,
+            )
+            .unwrap();
+        assert!(res.messages.is_empty());
+        assert!(owner.hooks(deps.as_ref().storage).unwrap().is_empty());
+    }
 
-        assert_eq!(err, OwnerError::NotOwner {})
+    /// Makes the address the only contract known to the querier
+    fn mock_contract(querier: &mut MockQuerier, contract: &'static str) {
+        querier.update_wasm(move |query| match query {
+            WasmQuery::ContractInfo { contract_addr } if contract_addr == contract => {
+                let info = ContractInfoResponse::new(1, "creator");
+                SystemResult::Ok(ContractResult::Ok(to_binary(&info).unwrap()))
+            }
+            WasmQuery::ContractInfo { contract_addr } => {
+                SystemResult::Err(SystemError::NoSuchContract {
+                    addr: contract_addr.clone(),
+                })
+            }
+            _ => SystemResult::Err(SystemError::UnsupportedRequest {
+                kind: "wasm".to_string(),
+            }),
+        });
     }
 
     #[test]
-    fn clear_proposed_permissions() {
+    fn proposed_owner_is_notified() {
         let mut deps = mock_dependencies();
-        let sender = Addr::unchecked("peter_parker");
-        let info = mock_info(sender.as_ref(), &[]);
-        let owner = Owner::new("xyz");
+        mock_contract(&mut deps.querier, "avengers_dao");
+        let owner = Owner::new("xyz").with_proposal_notifications();
 
         let mut_deps = deps.as_mut();
         owner
@@ -672,35 +4842,54 @@ mod tests {
                 mut_deps.storage,
                 mut_deps.api,
                 OwnerInit::SetInitialOwner {
-                    owner: sender.to_string(),
+                    owner: "peter_parker".to_string(),
                 },
             )
             .unwrap();
-        owner
+
+        let res = owner
             .update::<Empty, Empty>(
-                mut_deps,
-                info,
+                deps.as_mut(),
+                mock_env(),
+                mock_info("peter_parker", &[]),
                 ProposeNewOwner {
-                    proposed: "miles_morales".to_string(),
+                    proposed: "avengers_dao".to_string(),
+                    expiry: None,
                 },
             )
             .unwrap();
+        let expected: CosmosMsg = WasmMsg::Execute {
+            contract_addr: "avengers_dao".to_string(),
+            msg: to_binary(&OwnershipProposedMsg::OwnershipProposed(
+                OwnershipProposed {
+                    namespace: "xyz".to_string(),
+                    from: Addr::unchecked("peter_parker"),
+                },
+            ))
+            .unwrap(),
+            funds: vec![],
+        }
+        .into();
+        assert_eq!(res.messages.len(), 1);
+        assert_eq!(res.messages[0].msg, expected);
 
-        let bad_guy = Addr::unchecked("doc_oc");
-        let info = mock_info(bad_guy.as_ref(), &[]);
-        let err = owner
-            .update::<Empty, Empty>(deps.as_mut(), info, ClearProposed)
-            .unwrap_err();
-
-        assert_eq!(err, OwnerError::NotOwner {})
+        // Only the proposal is announced
+        let res = owner
+            .update::<Empty, Empty>(
+                deps.as_mut(),
+                mock_env(),
+                mock_info("avengers_dao", &[]),
+                AcceptProposed,
+            )
+            .unwrap();
+        assert!(res.messages.is_empty());
     }
 
     #[test]
-    fn accept_proposed_permissions() {
+    fn proposed_account_is_not_notified() {
         let mut deps = mock_dependencies();
-        let sender = Addr::unchecked("peter_parker");
-        let info = mock_info(sender.as_ref(), &[]);
-        let owner = Owner::new("xyz");
+        mock_contract(&mut deps.querier, "avengers_dao");
+        let owner = Owner::new("xyz").with_proposal_notifications();
 
         let mut_deps = deps.as_mut();
         owner
@@ -708,113 +4897,209 @@ mod tests {
                 mut_deps.storage,
                 mut_deps.api,
                 OwnerInit::SetInitialOwner {
-                    owner: sender.to_string(),
+                    owner: "peter_parker".to_string(),
                 },
             )
             .unwrap();
-        owner
+
+        let res = owner
             .update::<Empty, Empty>(
-                mut_deps,
-                info,
+                deps.as_mut(),
+                mock_env(),
+                mock_info("peter_parker", &[]),
                 ProposeNewOwner {
                     proposed: "miles_morales".to_string(),
+                    expiry: None,
                 },
             )
             .unwrap();
+        assert!(res.messages.is_empty());
 
-        let bad_guy = Addr::unchecked("doc_oc");
-        let info = mock_info(bad_guy.as_ref(), &[]);
-        let err = owner
-            .update::<Empty, Empty>(deps.as_mut(), info, AcceptProposed)
-            .unwrap_err();
-
-        assert_eq!(err, OwnerError::NotProposedOwner {})
+        let proposed = owner.proposed(deps.as_ref().storage).unwrap();
+        assert_eq!(proposed, Some(Addr::unchecked("miles_morales")));
     }
 
     #[test]
-    fn abolish_owner_role_permissions() {
+    fn multi_owner_proposal_notified_once_approved() {
         let mut deps = mock_dependencies();
-        let sender = Addr::unchecked("peter_parker");
-        let owner = Owner::new("xyz");
-
+        mock_contract(&mut deps.querier, "avengers_dao");
+        let owner = Owner::new("xyz").with_proposal_notifications();
         let mut_deps = deps.as_mut();
-        owner
-            .initialize(
-                mut_deps.storage,
-                mut_deps.api,
-                OwnerInit::SetInitialOwner {
-                    owner: sender.to_string(),
-                },
-            )
-            .unwrap();
-
-        let bad_guy = Addr::unchecked("doc_oc");
-        let info = mock_info(bad_guy.as_ref(), &[]);
-        let err = owner
-            .update::<Empty, Empty>(deps.as_mut(), info, AbolishOwnerRole)
-            .unwrap_err();
-
-        assert_eq!(err, OwnerError::NotOwner {})
-    }
-
-    #[cfg(feature = "emergency-owner")]
-    #[test]
-    fn set_emergency_owner_role_permissions() {
-        let mut deps = mock_dependencies();
-        let sender = Addr::unchecked("peter_parker");
-        let owner = Owner::new("xyz");
+        init_multi_owner(mut_deps.storage, mut_deps.api, &owner);
 
-        let mut_deps = deps.as_mut();
-        owner
-            .initialize(
-                mut_deps.storage,
-                mut_deps.api,
-                OwnerInit::SetInitialOwner {
-                    owner: sender.to_string(),
-                },
+        let propose = ProposeNewOwner {
+            proposed: "avengers_dao".to_string(),
+            expiry: None,
+        };
+        let res = owner
+            .update::<Empty, Empty>(
+                deps.as_mut(),
+                mock_env(),
+                mock_info("peter_parker", &[]),
+                propose.clone(),
             )
             .unwrap();
+        assert!(res.messages.is_empty());
 
-        let bad_guy = Addr::unchecked("doc_oc");
-        let info = mock_info(bad_guy.as_ref(), &[]);
-        let err = owner
+        let res = owner
             .update::<Empty, Empty>(
                 deps.as_mut(),
-                info,
-                SetEmergencyOwner {
-                    emergency_owner: bad_guy.to_string(),
-                },
+                mock_env(),
+                mock_info("miles_morales", &[]),
+                propose,
             )
-            .unwrap_err();
+            .unwrap();
+        assert_eq!(res.messages.len(), 1);
+        match &res.messages[0].msg {
+            CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr, msg, ..
+            }) => {
+                assert_eq!(contract_addr, "avengers_dao");
+                let OwnershipProposedMsg::OwnershipProposed(proposed) = from_binary(msg).unwrap();
+                assert_eq!(proposed.from, Addr::unchecked("miles_morales"));
+            }
+            msg => panic!("Unexpected message {:?}", msg),
+        }
+    }
+
+    //--------------------------------------------------------------------------------------------------
+    // Test transition table
+    //--------------------------------------------------------------------------------------------------
 
-        assert_eq!(err, OwnerError::NotOwner {})
+    /// One state per name, plus the multi-owner sub-states. Every timelock has already elapsed.
+    fn sample_states() -> Vec<OwnerState> {
+        let owner = Addr::unchecked("peter_parker");
+        let multi = MultiOwnerState {
+            owners: vec![owner.clone()],
+            threshold: 1,
+            pending: vec![PendingApproval {
+                action: "withdraw".to_string(),
+                approvals: vec![owner.clone()],
+            }],
+            proposed: None,
+            expiry: None,
+            proposed_at: Timestamp::from_seconds(0),
+            abolish_after: None,
+            #[cfg(feature = "emergency-owner")]
+            emergency_owner: Some(Addr::unchecked("gwen_stacy")),
+        };
+
+        vec![
+            OwnerState::Uninitialized,
+            OwnerState::Base {
+                owner: owner.clone(),
+                #[cfg(feature = "emergency-owner")]
+                emergency_owner: Some(Addr::unchecked("gwen_stacy")),
+            },
+            OwnerState::Proposed {
+                owner: owner.clone(),
+                proposed: Addr::unchecked("miles_morales"),
+                expiry: None,
+                proposed_at: Timestamp::from_seconds(0),
+                #[cfg(feature = "emergency-owner")]
+                emergency_owner: Some(Addr::unchecked("gwen_stacy")),
+            },
+            OwnerState::AbolitionProposed {
+                owner,
+                abolish_after: Timestamp::from_seconds(0),
+                #[cfg(feature = "emergency-owner")]
+                emergency_owner: Some(Addr::unchecked("gwen_stacy")),
+            },
+            OwnerState::Abolished,
+            OwnerState::MultiOwner(multi.clone()),
+            OwnerState::MultiOwner(MultiOwnerState {
+                proposed: Some(Addr::unchecked("miles_morales")),
+                ..multi.clone()
+            }),
+            OwnerState::MultiOwner(MultiOwnerState {
+                abolish_after: Some(Timestamp::from_seconds(0)),
+                ..multi
+            }),
+        ]
     }
 
-    #[cfg(feature = "emergency-owner")]
-    #[test]
-    fn clear_emergency_owner_role_permissions() {
-        let mut deps = mock_dependencies();
-        let sender = Addr::unchecked("peter_parker");
-        let owner = Owner::new("xyz");
+    fn all_updates() -> Vec<OwnerUpdate> {
+        vec![
+            ProposeNewOwner {
+                proposed: "miles_morales".to_string(),
+                expiry: None,
+            },
+            ClearProposed,
+            AcceptProposed,
+            AbolishOwnerRole,
+            CancelAbolition,
+            ConfirmAbolition,
+            #[cfg(feature = "emergency-owner")]
+            SetEmergencyOwner {
+                emergency_owner: "gwen_stacy".to_string(),
+            },
+            #[cfg(feature = "emergency-owner")]
+            ClearEmergencyOwner,
+            #[cfg(feature = "emergency-owner")]
+            ResignEmergencyOwner,
+            WithdrawApproval {
+                action: "withdraw".to_string(),
+            },
+        ]
+    }
 
-        let mut_deps = deps.as_mut();
-        owner
-            .initialize(
-                mut_deps.storage,
-                mut_deps.api,
-                OwnerInit::SetInitialOwner {
-                    owner: sender.to_string(),
-                },
-            )
-            .unwrap();
+    #[test]
+    fn runtime_matches_transition_table() {
+        let api = MockApi::default();
+        let block = mock_env().block;
+        let policy = OwnerPolicy::default();
+        let mut observed = BTreeSet::new();
+
+        let inits = [
+            OwnerInit::SetInitialOwner {
+                owner: "peter_parker".to_string(),
+            },
+            OwnerInit::SetInitialOwnerWithTimelock {
+                owner: "peter_parker".to_string(),
+                accept_delay: 100,
+            },
+            OwnerInit::SetInitialOwners {
+                owners: vec!["peter_parker".to_string()],
+                threshold: 1,
+            },
+            OwnerInit::AbolishOwnerRole,
+        ];
+        for init in inits {
+            let name = init.name();
+            let (state, _) = init.into_state(&api).unwrap();
+            observed.insert(("Uninitialized", name, state.name()));
+        }
 
-        let bad_guy = Addr::unchecked("doc_oc");
-        let info = mock_info(bad_guy.as_ref(), &[]);
-        let err = owner
-            .update::<Empty, Empty>(deps.as_mut(), info, ClearEmergencyOwner)
-            .unwrap_err();
+        for state in sample_states() {
+            for update in all_updates() {
+                // Whoever is allowed to make the update
+                let sender = match update {
+                    AcceptProposed => state.proposed(),
+                    #[cfg(feature = "emergency-owner")]
+                    ResignEmergencyOwner => state.emergency_owner(),
+                    _ => Some(Addr::unchecked("peter_parker")),
+                }
+                .unwrap_or_else(|| Addr::unchecked("peter_parker"));
+
+                let (from, event) = (state.name(), update.name());
+                match state
+                    .clone()
+                    .transition(&policy, &api, &block, &sender, update)
+                {
+                    Ok(new_state) => {
+                        observed.insert((from, event, new_state.name()));
+                    }
+                    Err(OwnerError::StateTransitionError { .. }) => {}
+                    Err(err) => panic!("{} in the {} state failed: {}", event, from, err),
+                }
+            }
+        }
 
-        assert_eq!(err, OwnerError::NotOwner {})
+        let declared: BTreeSet<_> = TRANSITIONS
+            .iter()
+            .map(|t| (t.from, t.event, t.to))
+            .collect();
+        assert_eq!(observed, declared);
     }
 
     //--------------------------------------------------------------------------------------------------
@@ -838,12 +5123,17 @@ mod tests {
         assert_eq!(
             res,
             OwnerResponse {
+                label: "xyz".to_string(),
                 owner: None,
                 proposed: None,
+                proposed_expiry: None,
+                accept_after: None,
                 initialized: false,
                 abolished: false,
+                abolish_after: None,
                 #[cfg(feature = "emergency-owner")]
                 emergency_owner: None,
+                multi_owner: None,
             }
         );
     }
@@ -889,12 +5179,17 @@ mod tests {
         assert_eq!(
             res,
             OwnerResponse {
+                label: "xyz".to_string(),
                 owner: Some(original_owner.to_string()),
                 proposed: None,
+                proposed_expiry: None,
+                accept_after: None,
                 initialized: true,
                 abolished: false,
+                abolish_after: None,
                 #[cfg(feature = "emergency-owner")]
                 emergency_owner: None,
+                multi_owner: None,
             }
         );
     }
@@ -921,9 +5216,11 @@ mod tests {
         owner
             .update::<Empty, Empty>(
                 mut_deps,
+                mock_env(),
                 info,
                 ProposeNewOwner {
                     proposed: "miles_morales".to_string(),
+                    expiry: None,
                 },
             )
             .unwrap();
@@ -948,12 +5245,17 @@ mod tests {
         assert_eq!(
             res,
             OwnerResponse {
+                label: "xyz".to_string(),
                 owner: Some(original_owner.to_string()),
                 proposed: Some(proposed_owner.to_string()),
+                proposed_expiry: None,
+                accept_after: Some(mock_env().block.time),
                 initialized: true,
                 abolished: false,
+                abolish_after: None,
                 #[cfg(feature = "emergency-owner")]
                 emergency_owner: None,
+                multi_owner: None,
             }
         );
     }
@@ -981,16 +5283,18 @@ mod tests {
         owner
             .update::<Empty, Empty>(
                 mut_deps,
+                mock_env(),
                 info.clone(),
                 ProposeNewOwner {
                     proposed: "miles_morales".to_string(),
+                    expiry: None,
                 },
             )
             .unwrap();
 
         let mut_deps = deps.as_mut();
         owner
-            .update::<Empty, Empty>(mut_deps, info, ClearProposed)
+            .update::<Empty, Empty>(mut_deps, mock_env(), info, ClearProposed)
             .unwrap();
 
         let storage = deps.as_mut().storage;
@@ -1013,12 +5317,17 @@ mod tests {
         assert_eq!(
             res,
             OwnerResponse {
+                label: "xyz".to_string(),
                 owner: Some(original_owner.to_string()),
                 proposed: None,
+                proposed_expiry: None,
+                accept_after: None,
                 initialized: true,
                 abolished: false,
+                abolish_after: None,
                 #[cfg(feature = "emergency-owner")]
                 emergency_owner: None,
+                multi_owner: None,
             }
         );
     }
@@ -1046,9 +5355,11 @@ mod tests {
         owner
             .update::<Empty, Empty>(
                 mut_deps,
+                mock_env(),
                 info,
                 ProposeNewOwner {
                     proposed: "miles_morales".to_string(),
+                    expiry: None,
                 },
             )
             .unwrap();
@@ -1056,7 +5367,7 @@ mod tests {
         let info = mock_info(proposed_owner.as_ref(), &[]);
         let mut_deps = deps.as_mut();
         owner
-            .update::<Empty, Empty>(mut_deps, info, AcceptProposed)
+            .update::<Empty, Empty>(mut_deps, mock_env(), info, AcceptProposed)
             .unwrap();
 
         let storage = deps.as_mut().storage;
@@ -1079,12 +5390,17 @@ mod tests {
         assert_eq!(
             res,
             OwnerResponse {
+                label: "xyz".to_string(),
                 owner: Some(proposed_owner.to_string()),
                 proposed: None,
+                proposed_expiry: None,
+                accept_after: None,
                 initialized: true,
                 abolished: false,
+                abolish_after: None,
                 #[cfg(feature = "emergency-owner")]
                 emergency_owner: None,
+                multi_owner: None,
             }
         );
     }
@@ -1107,9 +5423,25 @@ mod tests {
             )
             .unwrap();
 
+        let mut env = mock_env();
+        let abolish_after = env.block.time.plus_seconds(ABOLITION_DELAY);
         let mut_deps = deps.as_mut();
         owner
-            .update::<Empty, Empty>(mut_deps, info, AbolishOwnerRole)
+            .update::<Empty, Empty>(mut_deps, env.clone(), info.clone(), AbolishOwnerRole)
+            .unwrap();
+
+        let storage = deps.as_ref().storage;
+        let state = owner.state(storage).unwrap();
+        match state {
+            OwnerState::AbolitionProposed { .. } => {}
+            _ => panic!("Should be in the AbolitionProposed state"),
+        }
+        assert!(owner.is_owner(storage, &original_owner).unwrap());
+        assert_eq!(owner.abolish_after(storage).unwrap(), Some(abolish_after));
+
+        env.block.time = abolish_after;
+        owner
+            .update::<Empty, Empty>(deps.as_mut(), env, info, ConfirmAbolition)
             .unwrap();
 
         let storage = deps.as_mut().storage;
@@ -1132,12 +5464,17 @@ mod tests {
         assert_eq!(
             res,
             OwnerResponse {
+                label: "xyz".to_string(),
                 owner: None,
                 proposed: None,
+                proposed_expiry: None,
+                accept_after: None,
                 initialized: true,
                 abolished: true,
+                abolish_after: None,
                 #[cfg(feature = "emergency-owner")]
                 emergency_owner: None,
+                multi_owner: None,
             }
         );
     }
@@ -1177,17 +5514,23 @@ mod tests {
         assert_eq!(
             res,
             OwnerResponse {
+                label: "xyz".to_string(),
                 owner: Some(original_owner.to_string()),
                 proposed: None,
+                proposed_expiry: None,
+                accept_after: None,
                 initialized: true,
                 abolished: false,
+                abolish_after: None,
                 emergency_owner: None,
+                multi_owner: None,
             }
         );
 
         owner
             .update::<Empty, Empty>(
                 mut_deps,
+                mock_env(),
                 info,
                 SetEmergencyOwner {
                     emergency_owner: emergency_owner.to_string(),
@@ -1215,11 +5558,16 @@ mod tests {
         assert_eq!(
             res,
             OwnerResponse {
+                label: "xyz".to_string(),
                 owner: Some(original_owner.to_string()),
                 proposed: None,
+                proposed_expiry: None,
+                accept_after: None,
                 emergency_owner: Some(emergency_owner.to_string()),
                 initialized: true,
                 abolished: false,
+                abolish_after: None,
+                multi_owner: None,
             }
         );
     }
@@ -1248,6 +5596,7 @@ mod tests {
         owner
             .update::<Empty, Empty>(
                 mut_deps,
+                mock_env(),
                 info.clone(),
                 SetEmergencyOwner {
                     emergency_owner: emergency_owner.to_string(),
@@ -1256,7 +5605,7 @@ mod tests {
             .unwrap();
 
         owner
-            .update::<Empty, Empty>(deps.as_mut(), info, ClearEmergencyOwner)
+            .update::<Empty, Empty>(deps.as_mut(), mock_env(), info, ClearEmergencyOwner)
             .unwrap();
 
         let storage = deps.as_ref().storage;
@@ -1279,12 +5628,138 @@ mod tests {
         assert_eq!(
             res,
             OwnerResponse {
+                label: "xyz".to_string(),
                 owner: Some(original_owner.to_string()),
                 proposed: None,
+                proposed_expiry: None,
+                accept_after: None,
                 initialized: true,
                 abolished: false,
+                abolish_after: None,
                 emergency_owner: None,
+                multi_owner: None,
             }
         );
     }
+    #[cfg(feature = "emergency-owner")]
+    #[test]
+    fn rotate_emergency_owner_with_proposed() {
+        let mut deps = mock_dependencies();
+        let original_owner = Addr::unchecked("peter_parker");
+        let proposed_owner = Addr::unchecked("miles_morales");
+        let emergency_owner = Addr::unchecked("gwen_stacy");
+        let info = mock_info(original_owner.as_ref(), &[]);
+        let owner = Owner::new("xyz");
+
+        let mut_deps = deps.as_mut();
+        owner
+            .initialize(
+                mut_deps.storage,
+                mut_deps.api,
+                OwnerInit::SetInitialOwner {
+                    owner: original_owner.to_string(),
+                },
+            )
+            .unwrap();
+        owner
+            .update::<Empty, Empty>(
+                mut_deps,
+                mock_env(),
+                info.clone(),
+                ProposeNewOwner {
+                    proposed: proposed_owner.to_string(),
+                    expiry: None,
+                },
+            )
+            .unwrap();
+
+        owner
+            .update::<Empty, Empty>(
+                deps.as_mut(),
+                mock_env(),
+                info.clone(),
+                SetEmergencyOwner {
+                    emergency_owner: emergency_owner.to_string(),
+                },
+            )
+            .unwrap();
+
+        let storage = deps.as_ref().storage;
+        let state = owner.state(storage).unwrap();
+        match state {
+            OwnerState::Proposed { .. } => {}
+            _ => panic!("Should be in the Proposed state"),
+        }
+        assert!(owner.is_emergency_owner(storage, &emergency_owner).unwrap());
+        assert!(owner.is_proposed(storage, &proposed_owner).unwrap());
+
+        owner
+            .update::<Empty, Empty>(deps.as_mut(), mock_env(), info, ClearEmergencyOwner)
+            .unwrap();
+
+        let res = owner.query(deps.as_ref().storage).unwrap();
+        assert_eq!(res.emergency_owner, None);
+        assert_eq!(res.proposed, Some(proposed_owner.to_string()));
+
+        // Pending proposal is still acceptable afterwards
+        let info = mock_info(proposed_owner.as_ref(), &[]);
+        owner
+            .update::<Empty, Empty>(deps.as_mut(), mock_env(), info, AcceptProposed)
+            .unwrap();
+        assert!(owner
+            .is_owner(deps.as_ref().storage, &proposed_owner)
+            .unwrap());
+    }
+    #[cfg(feature = "emergency-owner")]
+    #[test]
+    fn resign_emergency_owner() {
+        let mut deps = mock_dependencies();
+        let original_owner = Addr::unchecked("peter_parker");
+        let emergency_owner = Addr::unchecked("miles_morales");
+        let info = mock_info(original_owner.as_ref(), &[]);
+        let owner = Owner::new("xyz");
+
+        let mut_deps = deps.as_mut();
+        owner
+            .initialize(
+                mut_deps.storage,
+                mut_deps.api,
+                OwnerInit::SetInitialOwner {
+                    owner: original_owner.to_string(),
+                },
+            )
+            .unwrap();
+        owner
+            .update::<Empty, Empty>(
+                mut_deps,
+                mock_env(),
+                info,
+                SetEmergencyOwner {
+                    emergency_owner: emergency_owner.to_string(),
+                },
+            )
+            .unwrap();
+
+        let info = mock_info(emergency_owner.as_ref(), &[]);
+        let res = owner
+            .update::<Empty, Empty>(deps.as_mut(), mock_env(), info, ResignEmergencyOwner)
+            .unwrap();
+        assert_eq!(
+            res.attributes,
+            vec![
+                Attribute::new("action", "resign_emergency_owner"),
+                Attribute::new("label", "xyz"),
+                Attribute::new("sender", emergency_owner.as_str()),
+            ]
+        );
+
+        let storage = deps.as_ref().storage;
+        let state = owner.state(storage).unwrap();
+        match state {
+            OwnerState::Base { .. } => {}
+            _ => panic!("Should be in the Base state"),
+        }
+        assert_eq!(owner.emergency_owner(storage).unwrap(), None);
+        assert!(owner.is_owner(storage, &original_owner).unwrap());
+    }
 }
@@ -0,0 +1,493 @@
+use std::fmt::Debug;
+
+use cosmwasm_std::{
+    Addr, Api, BlockInfo, CustomQuery, DepsMut, Env, MessageInfo, Order, Response, StdResult,
+    Storage,
+};
+use cw_storage_plus::{Bound, KeyDeserialize, Map, PrimaryKey};
+use schemars::JsonSchema;
+
+use crate::event::OwnerEvent;
+use crate::owner::{pending_approval_response, update_response, OwnerPolicy, OwnerState};
+use crate::{OwnerError, OwnerInit, OwnerResponse, OwnerUpdate};
+
+const DEFAULT_LIMIT: u32 = 10;
+const MAX_LIMIT: u32 = 30;
+
+/// Keys found by a scan, along with the last key scanned
+type ScanResult<T> = (Vec<T>, Option<T>);
+
+type OwnerResult<T> = Result<T, OwnerError>;
+
+/// The same owner state machine as Owner, kept separately for every key of a map. Lets a contract
+/// give each of its resources (vaults, credit accounts, markets) an owner of its own.
+pub struct OwnerMap<'a, K> {
+    namespace: &'a str,
+    states: Map<'a, K, OwnerState>,
+}
+
+impl<'a, K> OwnerMap<'a, K>
+where
+    K: PrimaryKey<'a> + KeyDeserialize + Clone,
+    K::Output: 'static,
+{
+    pub const fn new(namespace: &'a str) -> Self {
+        Self {
+            namespace,
+            states: Map::new(namespace),
+        }
+    }
+
+    fn state(&self, storage: &dyn Storage, key: K) -> StdResult<OwnerState> {
+        Ok(self
+            .states
+            .may_load(storage, key)?
+            .unwrap_or(OwnerState::Uninitialized))
+    }
+
+    fn policy_key(&self) -> String {
+        format!("{}_policy", self.namespace)
+    }
+
+    fn policy(&self, storage: &dyn Storage, key: K) -> StdResult<OwnerPolicy> {
+        let policy_key = self.policy_key();
+        let policies: Map<&[u8], OwnerPolicy> = Map::new(&policy_key);
+        Ok(policies
+            .may_load(storage, &key.joined_key())?
+            .unwrap_or_default())
+    }
+
+    //--------------------------------------------------------------------------------------------------
+    // Queries
+    //--------------------------------------------------------------------------------------------------
+    pub fn current(&self, storage: &dyn Storage, key: K) -> StdResult<Option<Addr>> {
+        Ok(self.state(storage, key)?.owner())
+    }
+
+    pub fn is_owner(&self, storage: &dyn Storage, key: K, addr: &Addr) -> StdResult<bool> {
+        Ok(self.current(storage, key)?.as_ref() == Some(addr))
+    }
+
+    pub fn proposed(&self, storage: &dyn Storage, key: K) -> StdResult<Option<Addr>> {
+        Ok(self.state(storage, key)?.proposed())
+    }
+
+    pub fn is_proposed(&self, storage: &dyn Storage, key: K, addr: &Addr) -> StdResult<bool> {
+        Ok(self.proposed(storage, key)?.as_ref() == Some(addr))
+    }
+
+    #[cfg(feature = "emergency-owner")]
+    pub fn emergency_owner(&self, storage: &dyn Storage, key: K) -> StdResult<Option<Addr>> {
+        Ok(self.state(storage, key)?.emergency_owner())
+    }
+
+    #[cfg(feature = "emergency-owner")]
+    pub fn is_emergency_owner(
+        &self,
+        storage: &dyn Storage,
+        key: K,
+        addr: &Addr,
+    ) -> StdResult<bool> {
+        Ok(self.emergency_owner(storage, key)?.as_ref() == Some(addr))
+    }
+
+    pub fn query(&self, storage: &dyn Storage, key: K) -> StdResult<OwnerResponse> {
+        let policy = self.policy(storage, key.clone())?;
+        Ok(self.state(storage, key)?.response(self.namespace, &policy))
+    }
+
+    /// Keys owned by the address among the next `limit` keys of the map, including those where it
+    /// is one of multiple owners. Also returns the last key scanned to continue from, or None once
+    /// the end of the map is reached. Use IndexedOwnerMap to list owned keys without a scan.
+    pub fn scan_owned_by(
+        &self,
+        storage: &dyn Storage,
+        owner: &Addr,
+        start_after: Option<K>,
+        limit: Option<u32>,
+    ) -> StdResult<ScanResult<K::Output>>
+    where
+        K::Output: Clone,
+    {
+        let start = start_after.map(Bound::exclusive);
+        let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+
+        let mut owned = vec![];
+        let mut scanned = 0;
+        let mut last = None;
+        for item in self
+            .states
+            .range(storage, start, None, Order::Ascending)
+            .take(limit)
+        {
+            let (key, state) = item?;
+            if state.owners().contains(owner) {
+                owned.push(key.clone());
+            }
+            scanned += 1;
+            last = Some(key);
+        }
+
+        Ok((owned, last.filter(|_| scanned == limit)))
+    }
+
+    //--------------------------------------------------------------------------------------------------
+    // Mutations
+    //--------------------------------------------------------------------------------------------------
+    /// Sets up the owner of a new key. Can only be called once per key.
+    pub fn initialize(
+        &self,
+        storage: &mut dyn Storage,
+        api: &dyn Api,
+        key: K,
+        init_action: OwnerInit,
+    ) -> OwnerResult<()> {
+        let new_state = self.init_state(storage, api, key.clone(), init_action)?;
+        self.states.save(storage, key, &new_state)?;
+        Ok(())
+    }
+
+    /// Composes execute responses for owner state updates of a key
+    pub fn update<C, Q: CustomQuery>(
+        &self,
+        deps: DepsMut<Q>,
+        env: Env,
+        info: MessageInfo,
+        key: K,
+        update: OwnerUpdate,
+    ) -> OwnerResult<Response<C>>
+    where
+        C: Clone + Debug + PartialEq + JsonSchema,
+    {
+        let (previous, new_state, policy) = self.next_state(
+            deps.storage,
+            deps.api,
+            &env.block,
+            &info.sender,
+            key.clone(),
+            update.clone(),
+        )?;
+        self.states.save(deps.storage, key, &new_state)?;
+
+        Ok(self.response(&update, &info.sender, &policy, &previous, &new_state)?)
+    }
+
+    /// Checks the key has not been initialized yet and stores its policy. Saving the returned
+    /// state is left to the caller.
+    pub(crate) fn init_state(
+        &self,
+        storage: &mut dyn Storage,
+        api: &dyn Api,
+        key: K,
+        init_action: OwnerInit,
+    ) -> OwnerResult<OwnerState> {
+        let state = self.state(storage, key.clone())?;
+        if !matches!(state, OwnerState::Uninitialized) {
+            return Err(OwnerError::StateTransitionError {
+                state: state.name().to_string(),
+                attempted: init_action.name().to_string(),
+            });
+        }
+
+        let (new_state, policy) = init_action.into_state(api)?;
+        if let Some(policy) = policy {
+            let policy_key = self.policy_key();
+            let policies: Map<&[u8], OwnerPolicy> = Map::new(&policy_key);
+            policies.save(storage, &key.joined_key(), &policy)?;
+        }
+        Ok(new_state)
+    }
+
+    /// Runs the update against the state of the key. Returns the previous and the new state,
+    /// along with the policy of the key.
+    pub(crate) fn next_state(
+        &self,
+        storage: &dyn Storage,
+        api: &dyn Api,
+        block: &BlockInfo,
+        sender: &Addr,
+        key: K,
+        update: OwnerUpdate,
+    ) -> OwnerResult<(OwnerState, OwnerState, OwnerPolicy)> {
+        let previous = self.state(storage, key.clone())?;
+        let policy = self.policy(storage, key)?;
+        let new_state = previous
+            .clone()
+            .transition(&policy, api, block, sender, update)?;
+        Ok((previous, new_state, policy))
+    }
+
+    pub(crate) fn response<C>(
+        &self,
+        update: &OwnerUpdate,
+        sender: &Addr,
+        policy: &OwnerPolicy,
+        previous: &OwnerState,
+        new_state: &OwnerState,
+    ) -> StdResult<Response<C>>
+    where
+        C: Clone + Debug + PartialEq + JsonSchema,
+    {
+        if new_state.awaits_approval(sender, update)? {
+            return Ok(pending_approval_response(self.namespace, sender));
+        }
+
+        let event = OwnerEvent::transition(
+            self.namespace,
+            update.name(),
+            sender,
+            policy,
+            previous,
+            new_state,
+        );
+        Ok(update_response(self.namespace, update, event))
+    }
+
+    //--------------------------------------------------------------------------------------------------
+    // Assertions
+    //--------------------------------------------------------------------------------------------------
+    /// Similar to is_owner() except it raises an exception if caller is not the key's owner. Always
+    /// fails for keys held by a group of owners.
+    pub fn assert_owner(&self, storage: &dyn Storage, key: K, caller: &Addr) -> OwnerResult<()> {
+        self.state(storage, key)?.assert_owner(caller)
+    }
+
+    pub fn assert_proposed(&self, storage: &dyn Storage, key: K, caller: &Addr) -> OwnerResult<()> {
+        self.state(storage, key)?.assert_proposed(caller)
+    }
+
+    #[cfg(feature = "emergency-owner")]
+    pub fn assert_emergency_owner(
+        &self,
+        storage: &dyn Storage,
+        key: K,
+        caller: &Addr,
+    ) -> OwnerResult<()> {
+        self.state(storage, key)?.assert_emergency_owner(caller)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use cosmwasm_std::testing::{mock_dependencies, mock_env, mock_info};
+    use cosmwasm_std::{Addr, Empty};
+
+    use crate::OwnerUpdate::{AcceptProposed, ProposeNewOwner};
+    use crate::{OwnerError, OwnerInit, OwnerMap};
+
+    const VAULT_OWNERS: OwnerMap<&str> = OwnerMap::new("vault_owners");
+
+    #[test]
+    fn keys_have_independent_owners() {
+        let mut deps = mock_dependencies();
+        let mut_deps = deps.as_mut();
+        for (vault, owner) in [("vault_a", "peter_parker"), ("vault_b", "miles_morales")] {
+            VAULT_OWNERS
+                .initialize(
+                    mut_deps.storage,
+                    mut_deps.api,
+                    vault,
+                    OwnerInit::SetInitialOwner {
+                        owner: owner.to_string(),
+                    },
+                )
+                .unwrap();
+        }
+
+        let err = VAULT_OWNERS
+            .initialize(
+                mut_deps.storage,
+                mut_deps.api,
+                "vault_a",
+                OwnerInit::AbolishOwnerRole,
+            )
+            .unwrap_err();
+        assert_eq!(
+            err,
+            OwnerError::StateTransitionError {
+                state: "Base".to_string(),
+                attempted: "abolish_owner_role".to_string(),
+            }
+        );
+
+        let storage = deps.as_ref().storage;
+        let peter = Addr::unchecked("peter_parker");
+        VAULT_OWNERS
+            .assert_owner(storage, "vault_a", &peter)
+            .unwrap();
+        let err = VAULT_OWNERS
+            .assert_owner(storage, "vault_b", &peter)
+            .unwrap_err();
+        assert_eq!(
+            err,
+            OwnerError::NotOwner {
+                expected: "miles_morales".to_string(),
+                actual: "peter_parker".to_string(),
+            }
+        );
+
+        let res = VAULT_OWNERS.query(storage, "vault_c").unwrap();
+        assert!(!res.initialized);
+        assert_eq!(res.label, "vault_owners");
+    }
+
+    #[test]
+    fn multi_owner_keys_report_pending_approvals() {
+        let mut deps = mock_dependencies();
+        let mut_deps = deps.as_mut();
+        VAULT_OWNERS
+            .initialize(
+                mut_deps.storage,
+                mut_deps.api,
+                "vault_a",
+                OwnerInit::SetInitialOwners {
+                    owners: vec!["peter_parker".to_string(), "miles_morales".to_string()],
+                    threshold: 2,
+                },
+            )
+            .unwrap();
+
+        let propose = ProposeNewOwner {
+            proposed: "gwen_stacy".to_string(),
+            expiry: None,
+        };
+        let res = VAULT_OWNERS
+            .update::<Empty, Empty>(
+                deps.as_mut(),
+                mock_env(),
+                mock_info("peter_parker", &[]),
+                "vault_a",
+                propose.clone(),
+            )
+            .unwrap();
+        assert_eq!(res.attributes[2].value, "pending_approval");
+        assert!(res.events.is_empty());
+
+        let res = VAULT_OWNERS
+            .update::<Empty, Empty>(
+                deps.as_mut(),
+                mock_env(),
+                mock_info("miles_morales", &[]),
+                "vault_a",
+                propose,
+            )
+            .unwrap();
+        assert!(res.attributes.iter().all(|attr| attr.key != "status"));
+        let proposed = VAULT_OWNERS.proposed(deps.as_ref().storage, "vault_a");
+        assert_eq!(proposed.unwrap(), Some(Addr::unchecked("gwen_stacy")));
+    }
+
+    #[test]
+    fn two_step_transfer_per_key() {
+        let mut deps = mock_dependencies();
+        let mut_deps = deps.as_mut();
+        for vault in ["vault_a", "vault_b"] {
+            VAULT_OWNERS
+                .initialize(
+                    mut_deps.storage,
+                    mut_deps.api,
+                    vault,
+                    OwnerInit::SetInitialOwnerWithTimelock {
+                        owner: "peter_parker".to_string(),
+                        accept_delay: if vault == "vault_a" { 0 } else { 100 },
+                    },
+                )
+                .unwrap();
+        }
+
+        for vault in ["vault_a", "vault_b"] {
+            VAULT_OWNERS
+                .update::<Empty, Empty>(
+                    deps.as_mut(),
+                    mock_env(),
+                    mock_info("peter_parker", &[]),
+                    vault,
+                    ProposeNewOwner {
+                        proposed: "miles_morales".to_string(),
+                        expiry: None,
+                    },
+                )
+                .unwrap();
+        }
+
+        let err = VAULT_OWNERS
+            .update::<Empty, Empty>(
+                deps.as_mut(),
+                mock_env(),
+                mock_info("miles_morales", &[]),
+                "vault_b",
+                AcceptProposed,
+            )
+            .unwrap_err();
+        assert_eq!(
+            err,
+            OwnerError::TimelockNotElapsed {
+                accept_after: mock_env().block.time.plus_seconds(100)
+            }
+        );
+
+        let res = VAULT_OWNERS
+            .update::<Empty, Empty>(
+                deps.as_mut(),
+                mock_env(),
+                mock_info("miles_morales", &[]),
+                "vault_a",
+                AcceptProposed,
+            )
+            .unwrap();
+        assert_eq!(res.attributes[2].value, "miles_morales");
+
+        let storage = deps.as_ref().storage;
+        let miles = Addr::unchecked("miles_morales");
+        assert!(VAULT_OWNERS.is_owner(storage, "vault_a", &miles).unwrap());
+        assert!(VAULT_OWNERS
+            .is_proposed(storage, "vault_b", &miles)
+            .unwrap());
+    }
+
+    #[test]
+    fn list_keys_owned_by_address() {
+        let mut deps = mock_dependencies();
+        let mut_deps = deps.as_mut();
+        for i in 0..15 {
+            let owner = if i % 3 == 0 {
+                "miles_morales"
+            } else {
+                "peter_parker"
+            };
+            VAULT_OWNERS
+                .initialize(
+                    mut_deps.storage,
+                    mut_deps.api,
+                    &format!("vault_{:02}", i),
+                    OwnerInit::SetInitialOwner {
+                        owner: owner.to_string(),
+                    },
+                )
+                .unwrap();
+        }
+
+        let storage = deps.as_ref().storage;
+        let peter = Addr::unchecked("peter_parker");
+        let (owned, last) = VAULT_OWNERS
+            .scan_owned_by(storage, &peter, None, None)
+            .unwrap();
+        assert_eq!(owned.len(), 6);
+        assert_eq!(owned[0], "vault_01");
+        assert_eq!(owned[5], "vault_08");
+        assert_eq!(last, Some("vault_09".to_string()));
+
+        let (owned, last) = VAULT_OWNERS
+            .scan_owned_by(storage, &peter, last.as_deref(), None)
+            .unwrap();
+        assert_eq!(owned, ["vault_10", "vault_11", "vault_13", "vault_14"]);
+        assert_eq!(last, None);
+
+        let miles = Addr::unchecked("miles_morales");
+        let (owned, last) = VAULT_OWNERS
+            .scan_owned_by(storage, &miles, Some("vault_03"), Some(4))
+            .unwrap();
+        assert_eq!(owned, ["vault_06"]);
+        assert_eq!(last, Some("vault_07".to_string()));
+    }
+}
@@ -0,0 +1,655 @@
+//! Model-based tests of the owner state machine. Random sequences of initializations, updates and
+//! waits from random senders are run against both Owner and a plain reference model of the
+//! single-owner and multi-owner states, which have to agree on every outcome.
+
+use cosmwasm_std::testing::{mock_dependencies, mock_env, mock_info};
+use cosmwasm_std::{to_vec, Addr, Empty, Timestamp};
+use cw_utils::Expiration;
+use proptest::collection::vec;
+use proptest::prelude::*;
+use proptest::strategy::Union;
+use proptest::test_runner::TestCaseError;
+
+use crate::{
+    MultiOwnerResponse, Owner, OwnerError, OwnerInit, OwnerResponse, OwnerUpdate, PendingApproval,
+    ABOLITION_DELAY, MAX_PENDING_APPROVALS,
+};
+
+const ADDRS: [&str; 3] = ["peter_parker", "miles_morales", "gwen_stacy"];
+
+#[derive(Clone, Debug)]
+enum Op {
+    Initialize(OwnerInit),
+    /// The expiry is given in seconds from the time of the update
+    Propose {
+        sender: String,
+        proposed: String,
+        expires_in: Option<u64>,
+    },
+    Update {
+        sender: String,
+        update: OwnerUpdate,
+    },
+    Wait(u64),
+}
+
+#[derive(Clone, Debug, PartialEq)]
+enum State {
+    Uninitialized,
+    Base {
+        owner: String,
+    },
+    Proposed {
+        owner: String,
+        proposed: String,
+        expiry: Option<Timestamp>,
+        proposed_at: Timestamp,
+    },
+    AbolitionProposed {
+        owner: String,
+        abolish_after: Timestamp,
+    },
+    Abolished,
+    MultiOwner(Multi),
+}
+
+#[derive(Clone, Debug, PartialEq)]
+struct Multi {
+    owners: Vec<String>,
+    threshold: u32,
+    /// Actions in the order they were first approved, with their approvals
+    pending: Vec<(String, Vec<String>)>,
+    proposed: Option<String>,
+    expiry: Option<Timestamp>,
+    proposed_at: Timestamp,
+    abolish_after: Option<Timestamp>,
+}
+
+impl Multi {
+    fn assert_member(&self, sender: &str) -> Result<(), OwnerError> {
+        if self.owners.iter().any(|owner| owner == sender) {
+            Ok(())
+        } else {
+            Err(OwnerError::NotOwner {
+                expected: self.owners.join(", "),
+                actual: sender.to_string(),
+            })
+        }
+    }
+
+    /// Returns true once the threshold is reached, dropping the approvals of the action
+    fn approve(&mut self, sender: &str, action: &str) -> Result<bool, OwnerError> {
+        self.assert_member(sender)?;
+        let approved = self
+            .pending
+            .iter()
+            .filter(|(_, approvals)| approvals.iter().any(|addr| addr == sender))
+            .count();
+        let idx = self.pending.iter().position(|(a, _)| a == action);
+        if idx.is_some_and(|idx| self.pending[idx].1.iter().any(|addr| addr == sender)) {
+            return Err(OwnerError::AlreadyApproved {});
+        }
+        if approved as u32 >= MAX_PENDING_APPROVALS {
+            return Err(OwnerError::TooManyPendingApprovals {
+                max: MAX_PENDING_APPROVALS,
+            });
+        }
+        let idx = idx.unwrap_or_else(|| {
+            self.pending.push((action.to_string(), vec![]));
+            self.pending.len() - 1
+        });
+        self.pending[idx].1.push(sender.to_string());
+        if self.pending[idx].1.len() as u32 >= self.threshold {
+            self.pending.remove(idx);
+            return Ok(true);
+        }
+        Ok(false)
+    }
+
+    fn withdraw(&mut self, sender: &str, action: &str) -> Result<(), OwnerError> {
+        self.assert_member(sender)?;
+        let idx = self
+            .pending
+            .iter()
+            .position(|(a, approvals)| a == action && approvals.iter().any(|addr| addr == sender))
+            .ok_or(OwnerError::ApprovalNotFound {})?;
+        self.pending[idx].1.retain(|addr| addr != sender);
+        if self.pending[idx].1.is_empty() {
+            self.pending.remove(idx);
+        }
+        Ok(())
+    }
+}
+
+impl State {
+    fn name(&self) -> &'static str {
+        match self {
+            State::Uninitialized => "Uninitialized",
+            State::Base { .. } => "Base",
+            State::Proposed { .. } => "Proposed",
+            State::AbolitionProposed { .. } => "AbolitionProposed",
+            State::Abolished => "Abolished",
+            State::MultiOwner(_) => "MultiOwner",
+        }
+    }
+
+    fn owner(&self) -> Option<&str> {
+        match self {
+            State::Base { owner }
+            | State::Proposed { owner, .. }
+            | State::AbolitionProposed { owner, .. } => Some(owner),
+            _ => None,
+        }
+    }
+}
+
+/// Reference implementation of the owner state machine
+#[derive(Clone, Debug)]
+struct Model {
+    state: State,
+    accept_delay: u64,
+    #[cfg(feature = "emergency-owner")]
+    emergency_owner: Option<String>,
+}
+
+impl Model {
+    fn new() -> Self {
+        Self {
+            state: State::Uninitialized,
+            accept_delay: 0,
+            #[cfg(feature = "emergency-owner")]
+            emergency_owner: None,
+        }
+    }
+
+    fn invalid(&self, attempted: &str) -> OwnerError {
+        OwnerError::StateTransitionError {
+            state: self.state.name().to_string(),
+            attempted: attempted.to_string(),
+        }
+    }
+
+    fn assert_owner(&self, sender: &str) -> Result<(), OwnerError> {
+        match self.state.owner() {
+            Some(owner) if owner == sender => Ok(()),
+            owner => Err(OwnerError::NotOwner {
+                expected: owner.unwrap_or("None").to_string(),
+                actual: sender.to_string(),
+            }),
+        }
+    }
+
+    fn initialize(&mut self, init: &OwnerInit) -> Result<(), OwnerError> {
+        if self.state != State::Uninitialized {
+            return Err(self.invalid(init.name()));
+        }
+        self.state = match init {
+            OwnerInit::SetInitialOwner { owner } => State::Base {
+                owner: owner.clone(),
+            },
+            OwnerInit::SetInitialOwnerWithTimelock {
+                owner,
+                accept_delay,
+            } => {
+                self.accept_delay = *accept_delay;
+                State::Base {
+                    owner: owner.clone(),
+                }
+            }
+            OwnerInit::AbolishOwnerRole => State::Abolished,
+            OwnerInit::SetInitialOwners { owners, threshold } => {
+                for (i, owner) in owners.iter().enumerate() {
+                    if owners[..i].contains(owner) {
+                        return Err(OwnerError::DuplicateOwner {});
+                    }
+                }
+                if *threshold == 0 || *threshold as usize > owners.len() {
+                    return Err(OwnerError::InvalidThreshold {});
+                }
+                State::MultiOwner(Multi {
+                    owners: owners.clone(),
+                    threshold: *threshold,
+                    pending: vec![],
+                    proposed: None,
+                    expiry: None,
+                    proposed_at: Timestamp::default(),
+                    abolish_after: None,
+                })
+            }
+        };
+        Ok(())
+    }
+
+    fn expiry_time(
+        &self,
+        now: Timestamp,
+        expiry: &Option<Expiration>,
+    ) -> Result<Option<Timestamp>, OwnerError> {
+        let expiry = expiry.map(|expiry| match expiry {
+            Expiration::AtTime(time) => time,
+            _ => unreachable!("only expiry by time is generated"),
+        });
+        if expiry.is_some_and(|time| time <= now.plus_seconds(self.accept_delay)) {
+            return Err(OwnerError::InvalidExpiry {});
+        }
+        Ok(expiry)
+    }
+
+    fn assert_acceptable(
+        &self,
+        now: Timestamp,
+        expiry: Option<Timestamp>,
+        proposed_at: Timestamp,
+    ) -> Result<(), OwnerError> {
+        if expiry.is_some_and(|time| now >= time) {
+            return Err(OwnerError::ProposalExpired {});
+        }
+        let accept_after = proposed_at.plus_seconds(self.accept_delay);
+        if now < accept_after {
+            return Err(OwnerError::TimelockNotElapsed { accept_after });
+        }
+        Ok(())
+    }
+
+    fn update_multi_owner(
+        &mut self,
+        mut multi: Multi,
+        now: Timestamp,
+        sender: &str,
+        update: &OwnerUpdate,
+    ) -> Result<State, OwnerError> {
+        let action = String::from_utf8(to_vec(update).unwrap()).unwrap();
+        // Every generated action is an owner update, so all approvals lapse on a state change
+        let new_state = match (multi.proposed.clone(), multi.abolish_after, update) {
+            (None, None, OwnerUpdate::ProposeNewOwner { proposed, expiry }) => {
+                let expiry = self.expiry_time(now, expiry)?;
+                if multi.approve(sender, &action)? {
+                    multi.proposed = Some(proposed.clone());
+                    multi.expiry = expiry;
+                    multi.proposed_at = now;
+                    multi.pending.clear();
+                }
+                State::MultiOwner(multi)
+            }
+            (None, None, OwnerUpdate::AbolishOwnerRole) => {
+                if multi.approve(sender, &action)? {
+                    multi.abolish_after =
+                        Some(now.plus_seconds(self.accept_delay.max(ABOLITION_DELAY)));
+                    multi.pending.clear();
+                }
+                State::MultiOwner(multi)
+            }
+            (_, Some(_), OwnerUpdate::CancelAbolition) => {
+                multi.assert_member(sender)?;
+                multi.abolish_after = None;
+                multi.pending.clear();
+                State::MultiOwner(multi)
+            }
+            (_, Some(abolish_after), OwnerUpdate::ConfirmAbolition) => {
+                multi.assert_member(sender)?;
+                if now < abolish_after {
+                    return Err(OwnerError::AbolitionTimelockNotElapsed { abolish_after });
+                }
+                #[cfg(feature = "emergency-owner")]
+                {
+                    self.emergency_owner = None;
+                }
+                State::Abolished
+            }
+            #[cfg(feature = "emergency-owner")]
+            (_, _, OwnerUpdate::SetEmergencyOwner { emergency_owner }) => {
+                if multi.approve(sender, &action)? {
+                    self.emergency_owner = Some(emergency_owner.clone());
+                    multi.pending.clear();
+                }
+                State::MultiOwner(multi)
+            }
+            #[cfg(feature = "emergency-owner")]
+            (_, _, OwnerUpdate::ClearEmergencyOwner) => {
+                if multi.approve(sender, &action)? {
+                    self.emergency_owner = None;
+                    multi.pending.clear();
+                }
+                State::MultiOwner(multi)
+            }
+            #[cfg(feature = "emergency-owner")]
+            (_, _, OwnerUpdate::ResignEmergencyOwner) => {
+                if self.emergency_owner.as_deref() != Some(sender) {
+                    return Err(OwnerError::NotEmergencyOwner {});
+                }
+                self.emergency_owner = None;
+                multi.pending.clear();
+                State::MultiOwner(multi)
+            }
+            (Some(proposed), _, OwnerUpdate::AcceptProposed) => {
+                if sender != proposed {
+                    return Err(OwnerError::NotProposedOwner {
+                        expected: proposed,
+                        actual: sender.to_string(),
+                    });
+                }
+                self.assert_acceptable(now, multi.expiry, multi.proposed_at)?;
+                State::Base { owner: proposed }
+            }
+            (Some(_), _, OwnerUpdate::ClearProposed) => {
+                multi.assert_member(sender)?;
+                multi.proposed = None;
+                multi.expiry = None;
+                multi.pending.clear();
+                State::MultiOwner(multi)
+            }
+            (_, _, OwnerUpdate::WithdrawApproval { action }) => {
+                multi.withdraw(sender, action)?;
+                State::MultiOwner(multi)
+            }
+            (_, _, update) => return Err(self.invalid(update.name())),
+        };
+        Ok(new_state)
+    }
+
+    fn update(
+        &mut self,
+        now: Timestamp,
+        sender: &str,
+        update: &OwnerUpdate,
+    ) -> Result<(), OwnerError> {
+        let new_state = match (self.state.clone(), update) {
+            (State::Base { owner }, OwnerUpdate::ProposeNewOwner { proposed, expiry }) => {
+                self.assert_owner(sender)?;
+                let expiry = self.expiry_time(now, expiry)?;
+                State::Proposed {
+                    owner,
+                    proposed: proposed.clone(),
+                    expiry,
+                    proposed_at: now,
+                }
+            }
+            (State::Base { owner }, OwnerUpdate::AbolishOwnerRole) => {
+                self.assert_owner(sender)?;
+                State::AbolitionProposed {
+                    owner,
+                    abolish_after: now.plus_seconds(self.accept_delay.max(ABOLITION_DELAY)),
+                }
+            }
+            (State::Proposed { owner, .. }, OwnerUpdate::ClearProposed) => {
+                self.assert_owner(sender)?;
+                State::Base { owner }
+            }
+            (
+                State::Proposed {
+                    proposed,
+                    expiry,
+                    proposed_at,
+                    ..
+                },
+                OwnerUpdate::AcceptProposed,
+            ) => {
+                if sender != proposed {
+                    return Err(OwnerError::NotProposedOwner {
+                        expected: proposed,
+                        actual: sender.to_string(),
+                    });
+                }
+                self.assert_acceptable(now, expiry, proposed_at)?;
+                State::Base { owner: proposed }
+            }
+            (State::AbolitionProposed { owner, .. }, OwnerUpdate::CancelAbolition) => {
+                self.assert_owner(sender)?;
+                State::Base { owner }
+            }
+            (State::AbolitionProposed { abolish_after, .. }, OwnerUpdate::ConfirmAbolition) => {
+                self.assert_owner(sender)?;
+                if now < abolish_after {
+                    return Err(OwnerError::AbolitionTimelockNotElapsed { abolish_after });
+                }
+                #[cfg(feature = "emergency-owner")]
+                {
+                    self.emergency_owner = None;
+                }
+                State::Abolished
+            }
+            #[cfg(feature = "emergency-owner")]
+            (state, OwnerUpdate::SetEmergencyOwner { emergency_owner })
+                if state.owner().is_some() =>
+            {
+                self.assert_owner(sender)?;
+                self.emergency_owner = Some(emergency_owner.clone());
+                state
+            }
+            #[cfg(feature = "emergency-owner")]
+            (state, OwnerUpdate::ClearEmergencyOwner) if state.owner().is_some() => {
+                self.assert_owner(sender)?;
+                self.emergency_owner = None;
+                state
+            }
+            #[cfg(feature = "emergency-owner")]
+            (state, OwnerUpdate::ResignEmergencyOwner) if state.owner().is_some() => {
+                if self.emergency_owner.as_deref() != Some(sender) {
+                    return Err(OwnerError::NotEmergencyOwner {});
+                }
+                self.emergency_owner = None;
+                state
+            }
+            (State::MultiOwner(multi), update) => {
+                self.update_multi_owner(multi, now, sender, update)?
+            }
+            (_, update) => return Err(self.invalid(update.name())),
+        };
+        self.state = new_state;
+        Ok(())
+    }
+
+    fn response(&self) -> OwnerResponse {
+        let (proposed, proposed_expiry, accept_after) = match &self.state {
+            State::Proposed {
+                proposed,
+                expiry,
+                proposed_at,
+                ..
+            }
+            | State::MultiOwner(Multi {
+                proposed: Some(proposed),
+                expiry,
+                proposed_at,
+                ..
+            }) => (
+                Some(proposed.clone()),
+                expiry.map(Expiration::AtTime),
+                Some(proposed_at.plus_seconds(self.accept_delay)),
+            ),
+            _ => (None, None, None),
+        };
+        OwnerResponse {
+            label: "xyz".to_string(),
+            owner: self.state.owner().map(String::from),
+            proposed,
+            proposed_expiry,
+            accept_after,
+            initialized: self.state != State::Uninitialized,
+            abolished: self.state == State::Abolished,
+            abolish_after: match &self.state {
+                State::AbolitionProposed { abolish_after, .. } => Some(*abolish_after),
+                State::MultiOwner(multi) => multi.abolish_after,
+                _ => None,
+            },
+            #[cfg(feature = "emergency-owner")]
+            emergency_owner: self.emergency_owner.clone(),
+            multi_owner: match &self.state {
+                State::MultiOwner(multi) => Some(MultiOwnerResponse {
+                    owners: multi.owners.clone(),
+                    threshold: multi.threshold,
+                    pending: multi
+                        .pending
+                        .iter()
+                        .map(|(action, approvals)| PendingApproval {
+                            action: action.clone(),
+                            approvals: approvals.iter().map(Addr::unchecked).collect(),
+                        })
+                        .collect(),
+                }),
+                _ => None,
+            },
+        }
+    }
+}
+
+//--------------------------------------------------------------------------------------------------
+// Strategies
+//--------------------------------------------------------------------------------------------------
+fn addr() -> impl Strategy<Value = String> {
+    prop::sample::select(ADDRS.to_vec()).prop_map(String::from)
+}
+
+fn owner_init() -> impl Strategy<Value = OwnerInit> {
+    prop_oneof![
+        1 => addr().prop_map(|owner| OwnerInit::SetInitialOwner { owner }),
+        1 => (addr(), 0..100u64).prop_map(|(owner, accept_delay)| {
+            OwnerInit::SetInitialOwnerWithTimelock {
+                owner,
+                accept_delay,
+            }
+        }),
+        1 => Just(OwnerInit::AbolishOwnerRole),
+        // Mostly valid groups, plus some with duplicate owners or a threshold out of range
+        2 => (prop::sample::subsequence(ADDRS.to_vec(), 1..=3), 1..=3u32).prop_map(
+            |(owners, threshold)| OwnerInit::SetInitialOwners {
+                owners: owners.into_iter().map(String::from).collect(),
+                threshold,
+            }
+        ),
+        1 => (vec(addr(), 1..=3), 0..=3u32)
+            .prop_map(|(owners, threshold)| OwnerInit::SetInitialOwners { owners, threshold }),
+    ]
+}
+
+/// Every update except ProposeNewOwner, which is generated separately
+fn owner_update() -> impl Strategy<Value = OwnerUpdate> {
+    let withdraw = prop_oneof![
+        approved_update(),
+        addr().prop_map(|proposed| OwnerUpdate::ProposeNewOwner {
+            proposed,
+            expiry: None,
+        }),
+    ]
+    .prop_map(|update| OwnerUpdate::WithdrawApproval {
+        action: String::from_utf8(to_vec(&update).unwrap()).unwrap(),
+    });
+    Union::new([approved_update().boxed(), withdraw.boxed()])
+}
+
+/// Updates other than ProposeNewOwner and WithdrawApproval
+fn approved_update() -> impl Strategy<Value = OwnerUpdate> {
+    #[allow(unused_mut)]
+    let mut updates = vec![
+        Just(OwnerUpdate::ClearProposed).boxed(),
+        Just(OwnerUpdate::AcceptProposed).boxed(),
+        Just(OwnerUpdate::AbolishOwnerRole).boxed(),
+        Just(OwnerUpdate::CancelAbolition).boxed(),
+        Just(OwnerUpdate::ConfirmAbolition).boxed(),
+    ];
+    #[cfg(feature = "emergency-owner")]
+    updates.extend([
+        addr()
+            .prop_map(|emergency_owner| OwnerUpdate::SetEmergencyOwner { emergency_owner })
+            .boxed(),
+        Just(OwnerUpdate::ClearEmergencyOwner).boxed(),
+        Just(OwnerUpdate::ResignEmergencyOwner).boxed(),
+    ]);
+    Union::new(updates)
+}
+
+fn op() -> impl Strategy<Value = Op> {
+    prop_oneof![
+        1 => owner_init().prop_map(Op::Initialize),
+        4 => (addr(), addr(), prop::option::of(0..200u64)).prop_map(
+            |(sender, proposed, expires_in)| Op::Propose {
+                sender,
+                proposed,
+                expires_in,
+            }
+        ),
+        6 => (addr(), owner_update()).prop_map(|(sender, update)| Op::Update { sender, update }),
+        2 => prop_oneof![0..100u64, Just(ABOLITION_DELAY)].prop_map(Op::Wait),
+    ]
+}
+
+//--------------------------------------------------------------------------------------------------
+// Tests
+//--------------------------------------------------------------------------------------------------
+fn run(init: Option<OwnerInit>, ops: Vec<Op>) -> Result<(), TestCaseError> {
+    let mut deps = mock_dependencies();
+    let mut env = mock_env();
+    let owner = Owner::new("xyz");
+    let mut model = Model::new();
+
+    for op in init.map(Op::Initialize).into_iter().chain(ops) {
+        let before = owner.query(deps.as_ref().storage).unwrap();
+
+        let (sender, update) = match op {
+            Op::Initialize(init) => {
+                let mut_deps = deps.as_mut();
+                let res = owner.initialize(mut_deps.storage, mut_deps.api, init.clone());
+                prop_assert_eq!(res, model.initialize(&init), "{}", init.name());
+                prop_assert_eq!(
+                    owner.query(deps.as_ref().storage).unwrap(),
+                    model.response()
+                );
+                continue;
+            }
+            Op::Wait(seconds) => {
+                env.block.time = env.block.time.plus_seconds(seconds);
+                env.block.height += 1;
+                continue;
+            }
+            Op::Propose {
+                sender,
+                proposed,
+                expires_in,
+            } => {
+                let expiry = expires_in.map(|s| Expiration::AtTime(env.block.time.plus_seconds(s)));
+                (sender, OwnerUpdate::ProposeNewOwner { proposed, expiry })
+            }
+            Op::Update { sender, update } => (sender, update),
+        };
+
+        let expected = model.update(env.block.time, &sender, &update);
+        let res = owner
+            .update::<Empty, Empty>(
+                deps.as_mut(),
+                env.clone(),
+                mock_info(&sender, &[]),
+                update.clone(),
+            )
+            .map(|_| ());
+        prop_assert_eq!(&res, &expected, "{} by {}", update.name(), sender);
+
+        let after = owner.query(deps.as_ref().storage).unwrap();
+        prop_assert_eq!(&after, &model.response());
+
+        // Failed updates leave the state untouched, and once abolished every update fails
+        if res.is_err() || before.abolished {
+            prop_assert!(res.is_err());
+            prop_assert_eq!(&after, &before);
+        }
+
+        if matches!(update, OwnerUpdate::AcceptProposed) && res.is_ok() {
+            // Only the proposed owner can accept
+            prop_assert_eq!(before.proposed.as_deref(), Some(sender.as_str()));
+            prop_assert_eq!(after.owner.as_deref(), Some(sender.as_str()));
+            // The emergency owner survives the handover
+            #[cfg(feature = "emergency-owner")]
+            prop_assert_eq!(&after.emergency_owner, &before.emergency_owner);
+        }
+    }
+    Ok(())
+}
+
+proptest! {
+    #[test]
+    fn runtime_matches_model(
+        init in prop::option::weighted(0.9, owner_init()),
+        ops in vec(op(), 1..50),
+    ) {
+        run(init, ops)?;
+    }
+}
@@ -0,0 +1,476 @@
+use std::fmt::Debug;
+
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{
+    to_vec, Addr, CustomQuery, DepsMut, MessageInfo, Order, Response, StdError, StdResult, Storage,
+};
+use cw_storage_plus::{Bound, Map};
+use schemars::JsonSchema;
+use thiserror::Error;
+
+use crate::{Owner, OwnerError};
+
+const DEFAULT_LIMIT: u32 = 10;
+const MAX_LIMIT: u32 = 30;
+
+/// Returned from Roles.query_role() and Roles.query_roles()
+#[cw_serde]
+pub struct RoleResponse {
+    pub role: String,
+    pub members: Vec<String>,
+}
+
+/// Errors returned from Roles updates and assertions
+#[derive(Error, Debug, PartialEq)]
+pub enum RolesError {
+    #[error("{0}")]
+    Std(#[from] StdError),
+
+    #[error("{0}")]
+    Owner(#[from] OwnerError),
+
+    #[error("Role {role} does not exist")]
+    RoleNotFound { role: String },
+
+    #[error("Role {role} already exists")]
+    RoleAlreadyExists { role: String },
+
+    #[error("{addr} already has the {role} role")]
+    AlreadyAssigned { role: String, addr: String },
+
+    #[error("{addr} does not have the {role} role")]
+    NotAssigned { role: String, addr: String },
+
+    #[error("Caller does not have the {role} role")]
+    MissingRole { role: String },
+}
+
+type RolesResult<T> = Result<T, RolesError>;
+
+#[cw_serde]
+pub enum RolesUpdate {
+    /// Registers a new role without members. Only owner can execute.
+    CreateRole { role: String },
+    /// Removes a role together with all of its members. Only owner can execute.
+    RemoveRole { role: String },
+    /// Grants a role to an address. Only owner can execute.
+    AssignRole { role: String, addr: String },
+    /// Takes a role away from an address. Only owner can execute.
+    RevokeRole { role: String, addr: String },
+}
+
+/// A registry of named roles (e.g. "risk_manager", "oracle_operator") managed by an Owner.
+/// Only the current owner of the linked Owner can create roles and change their members.
+pub struct Roles<'a, M = ()> {
+    namespace: &'a str,
+    owner: &'a Owner<'a, M>,
+    roles: Map<'a, &'a str, Vec<Addr>>,
+}
+
+impl<'a, M> Roles<'a, M> {
+    pub const fn new(namespace: &'a str, owner: &'a Owner<'a, M>) -> Self {
+        Self {
+            namespace,
+            owner,
+            roles: Map::new(namespace),
+        }
+    }
+
+    fn members(&self, storage: &dyn Storage, role: &str) -> RolesResult<Vec<Addr>> {
+        self.roles
+            .may_load(storage, role)?
+            .ok_or_else(|| RolesError::RoleNotFound {
+                role: role.to_string(),
+            })
+    }
+
+    //--------------------------------------------------------------------------------------------------
+    // Queries
+    //--------------------------------------------------------------------------------------------------
+    pub fn has_role(&self, storage: &dyn Storage, role: &str, addr: &Addr) -> StdResult<bool> {
+        Ok(self
+            .roles
+            .may_load(storage, role)?
+            .is_some_and(|members| members.contains(addr)))
+    }
+
+    pub fn query_role(&self, storage: &dyn Storage, role: &str) -> RolesResult<RoleResponse> {
+        Ok(RoleResponse {
+            role: role.to_string(),
+            members: self
+                .members(storage, role)?
+                .into_iter()
+                .map(Into::into)
+                .collect(),
+        })
+    }
+
+    pub fn query_roles(
+        &self,
+        storage: &dyn Storage,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    ) -> StdResult<Vec<RoleResponse>> {
+        let start = start_after.as_deref().map(Bound::exclusive);
+        let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+
+        self.roles
+            .range(storage, start, None, Order::Ascending)
+            .take(limit)
+            .map(|item| {
+                let (role, members) = item?;
+                Ok(RoleResponse {
+                    role,
+                    members: members.into_iter().map(Into::into).collect(),
+                })
+            })
+            .collect()
+    }
+
+    //--------------------------------------------------------------------------------------------------
+    // Mutations
+    //--------------------------------------------------------------------------------------------------
+    /// Composes execute responses for role updates
+    pub fn update<C, Q: CustomQuery>(
+        &self,
+        deps: DepsMut<Q>,
+        info: MessageInfo,
+        update: RolesUpdate,
+    ) -> RolesResult<Response<C>>
+    where
+        C: Clone + Debug + PartialEq + JsonSchema,
+    {
+        // In multi-owner mode this only goes through once enough owners approved the update. The
+        // namespace keeps approvals for registries sharing the same Owner apart.
+        let json = String::from_utf8(to_vec(&update)?).map_err(StdError::from)?;
+        let action = format!("{}:{}", self.namespace, json);
+        if !self
+            .owner
+            .assert_owner_threshold(deps.storage, &info.sender, &action)?
+        {
+            return Ok(Response::new()
+                .add_attribute("action", "update_roles")
+                .add_attribute("status", "pending_approval")
+                .add_attribute("sender", info.sender));
+        }
+
+        let (role, addr) = match update {
+            RolesUpdate::CreateRole { role } => {
+                if self.roles.has(deps.storage, &role) {
+                    return Err(RolesError::RoleAlreadyExists { role });
+                }
+                self.roles.save(deps.storage, &role, &vec![])?;
+                (role, None)
+            }
+            RolesUpdate::RemoveRole { role } => {
+                self.members(deps.storage, &role)?;
+                self.roles.remove(deps.storage, &role);
+                (role, None)
+            }
+            RolesUpdate::AssignRole { role, addr } => {
+                let validated = deps.api.addr_validate(&addr)?;
+                let mut members = self.members(deps.storage, &role)?;
+                if members.contains(&validated) {
+                    return Err(RolesError::AlreadyAssigned { role, addr });
+                }
+                members.push(validated.clone());
+                self.roles.save(deps.storage, &role, &members)?;
+                (role, Some(validated))
+            }
+            RolesUpdate::RevokeRole { role, addr } => {
+                let validated = deps.api.addr_validate(&addr)?;
+                let mut members = self.members(deps.storage, &role)?;
+                if !members.contains(&validated) {
+                    return Err(RolesError::NotAssigned { role, addr });
+                }
+                members.retain(|member| member != &validated);
+                self.roles.save(deps.storage, &role, &members)?;
+                (role, Some(validated))
+            }
+        };
+
+        Ok(Response::new()
+            .add_attribute("action", "update_roles")
+            .add_attribute("role", role)
+            .add_attribute("addr", addr.map_or_else(|| "None".to_string(), Into::into))
+            .add_attribute("sender", info.sender))
+    }
+
+    //--------------------------------------------------------------------------------------------------
+    // Assertions
+    //--------------------------------------------------------------------------------------------------
+    /// Similar to has_role() except it raises an exception if caller does not hold the role
+    pub fn assert_role(&self, storage: &dyn Storage, role: &str, caller: &Addr) -> RolesResult<()> {
+        if !self.has_role(storage, role, caller)? {
+            Err(RolesError::MissingRole {
+                role: role.to_string(),
+            })
+        } else {
+            Ok(())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::roles::{RoleResponse, Roles, RolesError, RolesUpdate};
+    use crate::{Owner, OwnerError, OwnerInit};
+    use cosmwasm_std::testing::{mock_dependencies, mock_info, MockApi, MockQuerier, MockStorage};
+    use cosmwasm_std::{Addr, DepsMut, Empty, OwnedDeps};
+
+    const OWNER: Owner = Owner::new("owner");
+    const ROLES: Roles = Roles::new("roles", &OWNER);
+
+    fn create_role(deps: DepsMut, role: &str) {
+        ROLES
+            .update::<Empty, Empty>(
+                deps,
+                mock_info("peter_parker", &[]),
+                RolesUpdate::CreateRole {
+                    role: role.to_string(),
+                },
+            )
+            .unwrap();
+    }
+
+    fn setup() -> OwnedDeps<MockStorage, MockApi, MockQuerier> {
+        let mut deps = mock_dependencies();
+        let mut_deps = deps.as_mut();
+        OWNER
+            .initialize(
+                mut_deps.storage,
+                mut_deps.api,
+                OwnerInit::SetInitialOwner {
+                    owner: "peter_parker".to_string(),
+                },
+            )
+            .unwrap();
+        deps
+    }
+
+    #[test]
+    fn only_owner_can_manage_roles() {
+        let mut deps = setup();
+
+        let err = ROLES
+            .update::<Empty, Empty>(
+                deps.as_mut(),
+                mock_info("doc_oc", &[]),
+                RolesUpdate::CreateRole {
+                    role: "risk_manager".to_string(),
+                },
+            )
+            .unwrap_err();
+        assert_eq!(
+            err,
+            RolesError::Owner(OwnerError::NotOwner {
+                expected: "peter_parker".to_string(),
+                actual: "doc_oc".to_string(),
+            })
+        );
+
+        create_role(deps.as_mut(), "risk_manager");
+
+        let err = ROLES
+            .update::<Empty, Empty>(
+                deps.as_mut(),
+                mock_info("doc_oc", &[]),
+                RolesUpdate::AssignRole {
+                    role: "risk_manager".to_string(),
+                    addr: "doc_oc".to_string(),
+                },
+            )
+            .unwrap_err();
+        assert!(matches!(
+            err,
+            RolesError::Owner(OwnerError::NotOwner { .. })
+        ));
+    }
+
+    #[test]
+    fn assign_and_revoke_role() {
+        let mut deps = setup();
+        let info = mock_info("peter_parker", &[]);
+        let member = Addr::unchecked("miles_morales");
+
+        let err = ROLES
+            .update::<Empty, Empty>(
+                deps.as_mut(),
+                info.clone(),
+                RolesUpdate::AssignRole {
+                    role: "risk_manager".to_string(),
+                    addr: member.to_string(),
+                },
+            )
+            .unwrap_err();
+        assert_eq!(
+            err,
+            RolesError::RoleNotFound {
+                role: "risk_manager".to_string()
+            }
+        );
+
+        create_role(deps.as_mut(), "risk_manager");
+        ROLES
+            .update::<Empty, Empty>(
+                deps.as_mut(),
+                info.clone(),
+                RolesUpdate::AssignRole {
+                    role: "risk_manager".to_string(),
+                    addr: member.to_string(),
+                },
+            )
+            .unwrap();
+
+        let storage = deps.as_ref().storage;
+        ROLES.assert_role(storage, "risk_manager", &member).unwrap();
+        let err = ROLES
+            .assert_role(storage, "oracle_operator", &member)
+            .unwrap_err();
+        assert_eq!(
+            err,
+            RolesError::MissingRole {
+                role: "oracle_operator".to_string()
+            }
+        );
+
+        let err = ROLES
+            .update::<Empty, Empty>(
+                deps.as_mut(),
+                info.clone(),
+                RolesUpdate::AssignRole {
+                    role: "risk_manager".to_string(),
+                    addr: member.to_string(),
+                },
+            )
+            .unwrap_err();
+        assert_eq!(
+            err,
+            RolesError::AlreadyAssigned {
+                role: "risk_manager".to_string(),
+                addr: member.to_string()
+            }
+        );
+
+        ROLES
+            .update::<Empty, Empty>(
+                deps.as_mut(),
+                info,
+                RolesUpdate::RevokeRole {
+                    role: "risk_manager".to_string(),
+                    addr: member.to_string(),
+                },
+            )
+            .unwrap();
+
+        let storage = deps.as_ref().storage;
+        assert!(!ROLES.has_role(storage, "risk_manager", &member).unwrap());
+        assert_eq!(
+            ROLES.query_role(storage, "risk_manager").unwrap(),
+            RoleResponse {
+                role: "risk_manager".to_string(),
+                members: vec![],
+            }
+        );
+    }
+
+    #[test]
+    fn create_and_remove_roles() {
+        let mut deps = setup();
+
+        create_role(deps.as_mut(), "risk_manager");
+        create_role(deps.as_mut(), "oracle_operator");
+
+        let err = ROLES
+            .update::<Empty, Empty>(
+                deps.as_mut(),
+                mock_info("peter_parker", &[]),
+                RolesUpdate::CreateRole {
+                    role: "risk_manager".to_string(),
+                },
+            )
+            .unwrap_err();
+        assert_eq!(
+            err,
+            RolesError::RoleAlreadyExists {
+                role: "risk_manager".to_string()
+            }
+        );
+
+        let roles = ROLES
+            .query_roles(deps.as_ref().storage, None, None)
+            .unwrap();
+        assert_eq!(roles.len(), 2);
+        assert_eq!(roles[0].role, "oracle_operator");
+
+        let roles = ROLES
+            .query_roles(
+                deps.as_ref().storage,
+                Some("oracle_operator".to_string()),
+                Some(1),
+            )
+            .unwrap();
+        assert_eq!(roles.len(), 1);
+        assert_eq!(roles[0].role, "risk_manager");
+
+        ROLES
+            .update::<Empty, Empty>(
+                deps.as_mut(),
+                mock_info("peter_parker", &[]),
+                RolesUpdate::RemoveRole {
+                    role: "risk_manager".to_string(),
+                },
+            )
+            .unwrap();
+
+        let roles = ROLES
+            .query_roles(deps.as_ref().storage, None, None)
+            .unwrap();
+        assert_eq!(roles.len(), 1);
+    }
+
+    #[test]
+    fn approvals_are_kept_per_registry() {
+        const OTHER_ROLES: Roles = Roles::new("other_roles", &OWNER);
+
+        let mut deps = mock_dependencies();
+        let mut_deps = deps.as_mut();
+        OWNER
+            .initialize(
+                mut_deps.storage,
+                mut_deps.api,
+                OwnerInit::SetInitialOwners {
+                    owners: vec!["peter_parker".to_string(), "miles_morales".to_string()],
+                    threshold: 2,
+                },
+            )
+            .unwrap();
+
+        let create = RolesUpdate::CreateRole {
+            role: "risk_manager".to_string(),
+        };
+        for (roles, sender) in [(&ROLES, "peter_parker"), (&OTHER_ROLES, "miles_morales")] {
+            let res = roles
+                .update::<Empty, Empty>(deps.as_mut(), mock_info(sender, &[]), create.clone())
+                .unwrap();
+            assert_eq!(res.attributes[1].value, "pending_approval");
+        }
+
+        let multi = OWNER.multi_owner(deps.as_ref().storage).unwrap().unwrap();
+        assert_eq!(multi.pending.len(), 2);
+        assert_eq!(
+            multi.pending[0].action,
+            "roles:{\"create_role\":{\"role\":\"risk_manager\"}}"
+        );
+
+        ROLES
+            .update::<Empty, Empty>(deps.as_mut(), mock_info("miles_morales", &[]), create)
+            .unwrap();
+        let storage = deps.as_ref().storage;
+        assert_eq!(ROLES.query_roles(storage, None, None).unwrap().len(), 1);
+        assert!(OTHER_ROLES
+            .query_roles(storage, None, None)
+            .unwrap()
+            .is_empty());
+    }
+}
@@ -0,0 +1,329 @@
+//! cw-multi-test helpers for contracts that embed Owner. Enabled by the `testing` feature.
+//!
+//! The helpers talk to contracts through the `update_owner` and `owner` messages added by
+//! #[owner_execute] and #[owner_query], so they work against any contract using those macros, not
+//! just the reference contract below.
+
+use std::fmt::Debug;
+
+use anyhow::Result as AnyResult;
+use cosmwasm_schema::{cw_serde, QueryResponses};
+use cosmwasm_std::{
+    Addr, Binary, CustomQuery, Deps, DepsMut, Empty, Env, MessageInfo, QuerierWrapper, Response,
+    StdResult,
+};
+use cw_multi_test::{App, AppResponse, Contract, ContractWrapper, Executor};
+use cw_utils::Expiration;
+use schemars::JsonSchema;
+
+use crate::{owner_execute, owner_query, Owner, OwnerError, OwnerEvent, OwnerInit, OwnerResponse};
+use crate::{OwnerUpdate, OWNER_EVENT_TYPE};
+
+//--------------------------------------------------------------------------------------------------
+// Reference contract
+//--------------------------------------------------------------------------------------------------
+const OWNER: Owner = Owner::new("owner");
+
+#[cw_serde]
+pub struct InstantiateMsg {
+    pub owner: OwnerInit,
+}
+
+#[owner_execute]
+#[cw_serde]
+pub enum ExecuteMsg {}
+
+#[owner_query]
+#[cw_serde]
+#[derive(QueryResponses)]
+pub enum QueryMsg {}
+
+pub fn instantiate(
+    deps: DepsMut,
+    _env: Env,
+    _info: MessageInfo,
+    msg: InstantiateMsg,
+) -> Result<Response, OwnerError> {
+    OWNER.initialize(deps.storage, deps.api, msg.owner)?;
+    Ok(Response::new())
+}
+
+pub fn execute(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: ExecuteMsg,
+) -> Result<Response, OwnerError> {
+    match msg {
+        ExecuteMsg::UpdateOwner(update) => OWNER.update(deps, env, info, update),
+    }
+}
+
+pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
+    match msg {
+        QueryMsg::Owner {} => OWNER.query_binary(deps.storage),
+    }
+}
+
+/// Minimal contract that does nothing but wrap an Owner
+pub fn owner_contract() -> Box<dyn Contract<Empty>> {
+    Box::new(ContractWrapper::new(execute, instantiate, query))
+}
+
+/// Stores and instantiates the reference contract with the given owner
+pub fn instantiate_owner_contract(app: &mut App, owner: &Addr) -> Addr {
+    let code_id = app.store_code(owner_contract());
+    app.instantiate_contract(
+        code_id,
+        owner.clone(),
+        &InstantiateMsg {
+            owner: OwnerInit::SetInitialOwner {
+                owner: owner.to_string(),
+            },
+        },
+        &[],
+        "mars-owner-reference",
+        None,
+    )
+    .unwrap()
+}
+
+//--------------------------------------------------------------------------------------------------
+// Flows
+//--------------------------------------------------------------------------------------------------
+/// Sends an owner update to the contract on behalf of `sender`
+pub fn update_owner<C>(
+    app: &mut impl Executor<C>,
+    contract: &Addr,
+    sender: &Addr,
+    update: OwnerUpdate,
+) -> AnyResult<AppResponse>
+where
+    C: Clone + Debug + PartialEq + JsonSchema + 'static,
+{
+    app.execute_contract(
+        sender.clone(),
+        contract.clone(),
+        &ExecuteMsg::UpdateOwner(update),
+        &[],
+    )
+}
+
+/// Drives the two-step transfer of the owner role of a contract. The proposed owner can be another
+/// contract, in which case the accept message is sent from its address.
+pub struct TransferFlow {
+    contract: Addr,
+    owner: Addr,
+    proposed: Addr,
+    expiry: Option<Expiration>,
+}
+
+impl TransferFlow {
+    pub fn new(contract: &Addr, owner: &Addr, proposed: &Addr) -> Self {
+        Self {
+            contract: contract.clone(),
+            owner: owner.clone(),
+            proposed: proposed.clone(),
+            expiry: None,
+        }
+    }
+
+    pub fn with_expiry(self, expiry: Expiration) -> Self {
+        Self {
+            expiry: Some(expiry),
+            ..self
+        }
+    }
+
+    pub fn propose<C>(&self, app: &mut impl Executor<C>) -> AnyResult<AppResponse>
+    where
+        C: Clone + Debug + PartialEq + JsonSchema + 'static,
+    {
+        update_owner(
+            app,
+            &self.contract,
+            &self.owner,
+            OwnerUpdate::ProposeNewOwner {
+                proposed: self.proposed.to_string(),
+                expiry: self.expiry,
+            },
+        )
+    }
+
+    pub fn accept<C>(&self, app: &mut impl Executor<C>) -> AnyResult<AppResponse>
+    where
+        C: Clone + Debug + PartialEq + JsonSchema + 'static,
+    {
+        update_owner(
+            app,
+            &self.contract,
+            &self.proposed,
+            OwnerUpdate::AcceptProposed,
+        )
+    }
+
+    pub fn clear<C>(&self, app: &mut impl Executor<C>) -> AnyResult<AppResponse>
+    where
+        C: Clone + Debug + PartialEq + JsonSchema + 'static,
+    {
+        update_owner(app, &self.contract, &self.owner, OwnerUpdate::ClearProposed)
+    }
+
+    /// Proposes and immediately accepts. Returns both responses.
+    pub fn run<C>(&self, app: &mut impl Executor<C>) -> AnyResult<(AppResponse, AppResponse)>
+    where
+        C: Clone + Debug + PartialEq + JsonSchema + 'static,
+    {
+        let proposed = self.propose(app)?;
+        let accepted = self.accept(app)?;
+        Ok((proposed, accepted))
+    }
+}
+
+//--------------------------------------------------------------------------------------------------
+// Assertions
+//--------------------------------------------------------------------------------------------------
+pub fn query_owner<Q: CustomQuery>(
+    querier: &QuerierWrapper<Q>,
+    contract: &Addr,
+) -> StdResult<OwnerResponse> {
+    querier.query_wasm_smart(contract, &QueryMsg::Owner {})
+}
+
+pub fn assert_owner<Q: CustomQuery>(
+    querier: &QuerierWrapper<Q>,
+    contract: &Addr,
+    expected: Option<&Addr>,
+) {
+    let res = query_owner(querier, contract).unwrap();
+    assert_eq!(
+        res.owner.as_deref(),
+        expected.map(Addr::as_str),
+        "unexpected owner of {}",
+        contract
+    );
+}
+
+pub fn assert_proposed<Q: CustomQuery>(
+    querier: &QuerierWrapper<Q>,
+    contract: &Addr,
+    expected: Option<&Addr>,
+) {
+    let res = query_owner(querier, contract).unwrap();
+    assert_eq!(
+        res.proposed.as_deref(),
+        expected.map(Addr::as_str),
+        "unexpected proposed owner of {}",
+        contract
+    );
+}
+
+/// Owner events emitted anywhere in the response, in order
+pub fn owner_events(res: &AppResponse) -> Vec<OwnerEvent> {
+    res.events
+        .iter()
+        .filter(|event| event.ty == format!("wasm-{}", OWNER_EVENT_TYPE))
+        .map(|event| OwnerEvent::try_from(event).unwrap())
+        .collect()
+}
+
+/// The last owner event of the response. Panics if there is none.
+pub fn last_owner_event(res: &AppResponse) -> OwnerEvent {
+    owner_events(res)
+        .pop()
+        .expect("response should contain an owner event")
+}
+
+pub fn assert_owner_event(res: &AppResponse, expected: &OwnerEvent) {
+    let events = owner_events(res);
+    assert!(
+        events.contains(expected),
+        "owner event {:?} not found in {:?}",
+        expected,
+        events
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use cosmwasm_std::Addr;
+    use cw_multi_test::App;
+    use cw_utils::Expiration;
+
+    use crate::testing::{
+        assert_owner, assert_owner_event, assert_proposed, instantiate_owner_contract,
+        last_owner_event, TransferFlow,
+    };
+    use crate::{OwnerError, OwnerEvent};
+
+    #[test]
+    fn transfer_to_another_contract() {
+        let mut app = App::default();
+        let peter = Addr::unchecked("peter_parker");
+        let vault = instantiate_owner_contract(&mut app, &peter);
+        let dao = instantiate_owner_contract(&mut app, &peter);
+
+        let flow = TransferFlow::new(&vault, &peter, &dao);
+        flow.propose(&mut app).unwrap();
+        assert_owner(&app.wrap(), &vault, Some(&peter));
+        assert_proposed(&app.wrap(), &vault, Some(&dao));
+
+        let res = flow.accept(&mut app).unwrap();
+        assert_owner(&app.wrap(), &vault, Some(&dao));
+        assert_proposed(&app.wrap(), &vault, None);
+        assert_owner_event(
+            &res,
+            &OwnerEvent {
+                namespace: "owner".to_string(),
+                action: "accept_proposed".to_string(),
+                sender: dao.clone(),
+                previous_owner: Some(peter.clone()),
+                owner: Some(dao.clone()),
+                previous_proposed: Some(dao.clone()),
+                proposed: None,
+                previous_proposed_expiry: None,
+                proposed_expiry: None,
+                previous_accept_after: Some(app.block_info().time),
+                accept_after: None,
+                previous_abolish_after: None,
+                abolish_after: None,
+                previous_owners: vec![],
+                owners: vec![],
+                previous_threshold: None,
+                threshold: None,
+                #[cfg(feature = "emergency-owner")]
+                previous_emergency_owner: None,
+                #[cfg(feature = "emergency-owner")]
+                emergency_owner: None,
+            },
+        );
+
+        // The other contract is unaffected
+        assert_owner(&app.wrap(), &dao, Some(&peter));
+    }
+
+    #[test]
+    fn expired_proposal_cannot_be_accepted() {
+        let mut app = App::default();
+        let peter = Addr::unchecked("peter_parker");
+        let miles = Addr::unchecked("miles_morales");
+        let vault = instantiate_owner_contract(&mut app, &peter);
+
+        let height = app.block_info().height;
+        let flow = TransferFlow::new(&vault, &peter, &miles)
+            .with_expiry(Expiration::AtHeight(height + 10));
+        let res = flow.propose(&mut app).unwrap();
+        assert_eq!(last_owner_event(&res).proposed, Some(miles.clone()));
+
+        app.update_block(|block| block.height += 10);
+        let err = flow.accept(&mut app).unwrap_err();
+        assert_eq!(
+            err.root_cause().to_string(),
+            OwnerError::ProposalExpired {}.to_string()
+        );
+
+        flow.clear(&mut app).unwrap();
+        assert_owner(&app.wrap(), &vault, Some(&peter));
+        assert_proposed(&app.wrap(), &vault, None);
+    }
+}
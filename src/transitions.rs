@@ -0,0 +1,192 @@
+use std::collections::BTreeMap;
+
+/// A valid transition of the owner state machine, triggered by an OwnerInit or OwnerUpdate
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Transition {
+    pub from: &'static str,
+    /// The snake_case name of the OwnerInit or OwnerUpdate variant
+    pub event: &'static str,
+    pub to: &'static str,
+}
+
+const fn transition(from: &'static str, event: &'static str, to: &'static str) -> Transition {
+    Transition { from, event, to }
+}
+
+pub const STATES: &[&str] = &[
+    "Uninitialized",
+    "Base",
+    "Proposed",
+    "AbolitionProposed",
+    "MultiOwner",
+    "Abolished",
+];
+
+pub const INITIAL_STATE: &str = "Uninitialized";
+
+pub const FINAL_STATE: &str = "Abolished";
+
+/// Every valid transition for the enabled features. Anything not listed fails with
+/// OwnerError::StateTransitionError. Multi-owner proposals and abolition stay in MultiOwner.
+pub const TRANSITIONS: &[Transition] = &[
+    transition("Uninitialized", "set_initial_owner", "Base"),
+    transition("Uninitialized", "set_initial_owner_with_timelock", "Base"),
+    transition("Uninitialized", "set_initial_owners", "MultiOwner"),
+    transition("Uninitialized", "abolish_owner_role", "Abolished"),
+    transition("Base", "propose_new_owner", "Proposed"),
+    transition("Base", "abolish_owner_role", "AbolitionProposed"),
+    #[cfg(feature = "emergency-owner")]
+    transition("Base", "set_emergency_owner", "Base"),
+    #[cfg(feature = "emergency-owner")]
+    transition("Base", "clear_emergency_owner", "Base"),
+    #[cfg(feature = "emergency-owner")]
+    transition("Base", "resign_emergency_owner", "Base"),
+    transition("Proposed", "accept_proposed", "Base"),
+    transition("Proposed", "clear_proposed", "Base"),
+    #[cfg(feature = "emergency-owner")]
+    transition("Proposed", "set_emergency_owner", "Proposed"),
+    #[cfg(feature = "emergency-owner")]
+    transition("Proposed", "clear_emergency_owner", "Proposed"),
+    #[cfg(feature = "emergency-owner")]
+    transition("Proposed", "resign_emergency_owner", "Proposed"),
+    transition("AbolitionProposed", "cancel_abolition", "Base"),
+    transition("AbolitionProposed", "confirm_abolition", "Abolished"),
+    #[cfg(feature = "emergency-owner")]
+    transition(
+        "AbolitionProposed",
+        "set_emergency_owner",
+        "AbolitionProposed",
+    ),
+    #[cfg(feature = "emergency-owner")]
+    transition(
+        "AbolitionProposed",
+        "clear_emergency_owner",
+        "AbolitionProposed",
+    ),
+    #[cfg(feature = "emergency-owner")]
+    transition(
+        "AbolitionProposed",
+        "resign_emergency_owner",
+        "AbolitionProposed",
+    ),
+    transition("MultiOwner", "propose_new_owner", "MultiOwner"),
+    transition("MultiOwner", "clear_proposed", "MultiOwner"),
+    transition("MultiOwner", "accept_proposed", "Base"),
+    transition("MultiOwner", "abolish_owner_role", "MultiOwner"),
+    transition("MultiOwner", "cancel_abolition", "MultiOwner"),
+    transition("MultiOwner", "confirm_abolition", "Abolished"),
+    transition("MultiOwner", "withdraw_approval", "MultiOwner"),
+    #[cfg(feature = "emergency-owner")]
+    transition("MultiOwner", "set_emergency_owner", "MultiOwner"),
+    #[cfg(feature = "emergency-owner")]
+    transition("MultiOwner", "clear_emergency_owner", "MultiOwner"),
+    #[cfg(feature = "emergency-owner")]
+    transition("MultiOwner", "resign_emergency_owner", "MultiOwner"),
+];
+
+/// The state machine as a Mermaid state diagram
+pub fn to_mermaid() -> String {
+    let mut out = String::from("stateDiagram-v2\n");
+    out += &format!("    [*] --> {}\n", INITIAL_STATE);
+    for t in TRANSITIONS {
+        out += &format!("    {} --> {}: {}\n", t.from, t.to, t.event);
+    }
+    out += &format!("    {} --> [*]\n", FINAL_STATE);
+    out
+}
+
+/// The state machine as a Graphviz digraph
+pub fn to_dot() -> String {
+    let mut out = String::from("digraph owner {\n    rankdir=LR;\n");
+    out += &format!("    {} [shape=doublecircle];\n", FINAL_STATE);
+    for t in TRANSITIONS {
+        out += &format!("    {} -> {} [label=\"{}\"];\n", t.from, t.to, t.event);
+    }
+    out += "}\n";
+    out
+}
+
+/// The state machine as an XState machine config, e.g. for the Stately.ai visualizer
+pub fn to_xstate_json() -> String {
+    let mut on: BTreeMap<&str, Vec<&Transition>> = BTreeMap::new();
+    for t in TRANSITIONS {
+        on.entry(t.from).or_default().push(t);
+    }
+
+    let states = STATES
+        .iter()
+        .map(|state| {
+            if *state == FINAL_STATE {
+                return format!("    \"{}\": {{ \"type\": \"final\" }}", state);
+            }
+            let events = on
+                .get(state)
+                .into_iter()
+                .flatten()
+                .map(|t| format!("\"{}\": \"{}\"", t.event, t.to))
+                .collect::<Vec<_>>()
+                .join(", ");
+            format!("    \"{}\": {{ \"on\": {{ {} }} }}", state, events)
+        })
+        .collect::<Vec<_>>()
+        .join(",\n");
+
+    format!(
+        "{{\n  \"id\": \"owner\",\n  \"initial\": \"{}\",\n  \"states\": {{\n{}\n  }}\n}}\n",
+        INITIAL_STATE, states
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeSet;
+
+    use crate::transitions::{to_dot, to_mermaid, to_xstate_json, STATES, TRANSITIONS};
+
+    #[test]
+    fn one_target_per_state_and_event() {
+        let keys: BTreeSet<_> = TRANSITIONS.iter().map(|t| (t.from, t.event)).collect();
+        assert_eq!(keys.len(), TRANSITIONS.len());
+
+        for t in TRANSITIONS {
+            assert!(STATES.contains(&t.from) && STATES.contains(&t.to));
+        }
+    }
+
+    #[test]
+    fn exports_every_transition() {
+        let mermaid = to_mermaid();
+        assert!(mermaid.starts_with("stateDiagram-v2\n    [*] --> Uninitialized\n"));
+        assert!(mermaid.ends_with("    Abolished --> [*]\n"));
+
+        let dot = to_dot();
+        assert!(dot.contains("    Abolished [shape=doublecircle];\n"));
+
+        let xstate = to_xstate_json();
+        assert!(xstate.contains("  \"initial\": \"Uninitialized\",\n"));
+        assert!(xstate.contains("    \"Abolished\": { \"type\": \"final\" }"));
+
+        for t in TRANSITIONS {
+            assert!(mermaid.contains(&format!("    {} --> {}: {}\n", t.from, t.to, t.event)));
+            assert!(dot.contains(&format!(
+                "    {} -> {} [label=\"{}\"];\n",
+                t.from, t.to, t.event
+            )));
+            // Each state and its events are on one line
+            let state = xstate
+                .lines()
+                .find(|line| line.starts_with(&format!("    \"{}\": ", t.from)))
+                .unwrap();
+            assert!(state.contains(&format!("\"{}\": \"{}\"", t.event, t.to)));
+        }
+    }
+
+    /// The README shows the diagram without features, so it has to be regenerated when the table
+    /// changes
+    #[cfg(not(feature = "emergency-owner"))]
+    #[test]
+    fn readme_diagram_is_current() {
+        let readme = include_str!("../README.md");
+        assert!(readme.contains(&format!("```mermaid\n{}```", to_mermaid())));
+    }
+}